@@ -0,0 +1,27 @@
+//! Call overhead on recursive workloads; run with `cargo bench --bench calls`.
+mod common;
+
+use common::run_workloads;
+
+fn main() {
+    run_workloads(&[
+        (
+            "fib(25)",
+            "fn fib(n) {
+                if n < 2 { return n }
+                return fib(n - 1) + fib(n - 2)
+            }
+            result = fib(25)",
+        ),
+        (
+            "method fib(22)",
+            "class Fib {
+                fn calc(self, n) {
+                    if n < 2 { return n }
+                    return self.calc(n - 1) + self.calc(n - 2)
+                }
+            }
+            result = Fib().calc(22)",
+        ),
+    ]);
+}
@@ -0,0 +1,37 @@
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 5;
+
+/// Runs each `(name, source)` script through the release interpreter and prints its timings.
+pub fn run_workloads(workloads: &[(&str, &str)]) {
+    for (name, source) in workloads {
+        let script_path =
+            std::env::temp_dir().join(format!("shriky-bench-{}.pik", std::process::id()));
+        std::fs::write(&script_path, source).expect("failed to write the benchmark script");
+        let timings: Vec<Duration> = (0..RUNS).map(|_| time_run(&script_path)).collect();
+        std::fs::remove_file(&script_path).ok();
+        let best = timings.iter().min().unwrap();
+        let mean = timings.iter().sum::<Duration>() / RUNS as u32;
+        println!(
+            "{:<16} best {:>8.1?}  mean {:>8.1?}  ({} runs)",
+            name, best, mean, RUNS
+        );
+    }
+}
+
+fn time_run(script_path: &std::path::Path) -> Duration {
+    let start = Instant::now();
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(script_path)
+        .output()
+        .expect("failed to run the interpreter");
+    let elapsed = start.elapsed();
+    // an uncaught exception exits non-zero with its traceback on stderr
+    assert!(
+        output.status.success(),
+        "benchmark script failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    elapsed
+}
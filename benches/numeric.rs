@@ -0,0 +1,27 @@
+//! Arithmetic in tight loops; run with `cargo bench --bench numeric`.
+mod common;
+
+use common::run_workloads;
+
+fn main() {
+    run_workloads(&[
+        (
+            "int loop(1M)",
+            "i = 0
+            total = 0
+            while i < 1000000 {
+                total = total + i * i % 7
+                i = i + 1
+            }",
+        ),
+        (
+            "float loop(1M)",
+            "i = 0
+            x = 0.0
+            while i < 1000000 {
+                x = x * 0.5 + 1.5
+                i += 1
+            }",
+        ),
+    ]);
+}
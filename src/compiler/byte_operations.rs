@@ -1,17 +1,28 @@
 use std::fmt;
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ByteOp {
     LoadConstant,
     LoadLocal,
     LoadNonlocal,
     LoadScope,
     LoadNull,
+    LoadBuiltin,
+    LoadReference,
+    LoadNonlocalReference,
+    Deref,
 
     MakeList,
+    MakeTuple,
     MakeMap,
     MakeClass,
+    MakeSlice,
+    UnpackSequence,
+    ListAppend,
+    ListExtend,
+    MapInsert,
+    MapUpdate,
 
     // unary
     Negate,
@@ -27,6 +38,13 @@ pub enum ByteOp {
     Exp,
 
     Compare,
+    Contains,
+
+    MatchSequence,
+    MatchMapping,
+    MatchClass,
+    LoadMatchArg,
+    Import,
 
     LogicalAnd,
     LogicalOr,
@@ -38,8 +56,11 @@ pub enum ByteOp {
     PostAssign,
     AssignSubscribe,
     AssignAttribute,
+    StoreDeref,
 
     Call,
+    CallSpread,
+    TailCall,
 
     // return
     ReturnValue,
@@ -49,6 +70,112 @@ pub enum ByteOp {
     Jump,
 }
 
+/// Every operation, indexed by the byte it is encoded as.
+const OPERATIONS: [ByteOp; 51] = [
+    ByteOp::LoadConstant,
+    ByteOp::LoadLocal,
+    ByteOp::LoadNonlocal,
+    ByteOp::LoadScope,
+    ByteOp::LoadNull,
+    ByteOp::LoadBuiltin,
+    ByteOp::LoadReference,
+    ByteOp::LoadNonlocalReference,
+    ByteOp::Deref,
+    ByteOp::MakeList,
+    ByteOp::MakeTuple,
+    ByteOp::MakeMap,
+    ByteOp::MakeClass,
+    ByteOp::MakeSlice,
+    ByteOp::UnpackSequence,
+    ByteOp::ListAppend,
+    ByteOp::ListExtend,
+    ByteOp::MapInsert,
+    ByteOp::MapUpdate,
+    ByteOp::Negate,
+    ByteOp::Not,
+    ByteOp::Add,
+    ByteOp::Sub,
+    ByteOp::Mul,
+    ByteOp::Div,
+    ByteOp::IntDiv,
+    ByteOp::Mod,
+    ByteOp::Exp,
+    ByteOp::Compare,
+    ByteOp::Contains,
+    ByteOp::MatchSequence,
+    ByteOp::MatchMapping,
+    ByteOp::MatchClass,
+    ByteOp::LoadMatchArg,
+    ByteOp::Import,
+    ByteOp::LogicalAnd,
+    ByteOp::LogicalOr,
+    ByteOp::BinarySubscribe,
+    ByteOp::AccessAttribute,
+    ByteOp::PreAssign,
+    ByteOp::PostAssign,
+    ByteOp::AssignSubscribe,
+    ByteOp::AssignAttribute,
+    ByteOp::StoreDeref,
+    ByteOp::Call,
+    ByteOp::CallSpread,
+    ByteOp::TailCall,
+    ByteOp::ReturnValue,
+    ByteOp::Pop,
+    ByteOp::PopJumpIfFalse,
+    ByteOp::Jump,
+];
+
+// keeps the table in step with the discriminants
+const _: () = {
+    let mut byte = 0;
+    while byte < OPERATIONS.len() {
+        assert!(OPERATIONS[byte] as usize == byte);
+        byte += 1;
+    }
+};
+
+impl ByteOp {
+    /// The operation a code byte stands for, if any.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        OPERATIONS.get(byte as usize).copied()
+    }
+
+    /// Whether the operation is encoded with an operand; the others ignore theirs.
+    pub fn has_operand(self) -> bool {
+        matches!(
+            self,
+            ByteOp::LoadConstant
+                | ByteOp::LoadLocal
+                | ByteOp::LoadNonlocal
+                | ByteOp::LoadScope
+                | ByteOp::LoadBuiltin
+                | ByteOp::LoadReference
+                | ByteOp::LoadNonlocalReference
+                | ByteOp::MakeList
+                | ByteOp::MakeTuple
+                | ByteOp::MakeMap
+                | ByteOp::MakeClass
+                | ByteOp::UnpackSequence
+                | ByteOp::Compare
+                | ByteOp::Contains
+                | ByteOp::MatchSequence
+                | ByteOp::LoadMatchArg
+                | ByteOp::Import
+                | ByteOp::PreAssign
+                | ByteOp::PostAssign
+                | ByteOp::Call
+                | ByteOp::TailCall
+                | ByteOp::PopJumpIfFalse
+                | ByteOp::Jump
+        )
+    }
+
+    /// Whether the operand is the position execution continues at.
+    pub fn is_jump(self) -> bool {
+        matches!(self, ByteOp::Jump | ByteOp::PopJumpIfFalse)
+    }
+}
+
 #[repr(u8)]
 #[derive(Debug, Clone, Copy)]
 pub enum ByteComparisonOp {
@@ -60,10 +187,20 @@ pub enum ByteComparisonOp {
     GreaterEqual,
 }
 
-impl From<u8> for ByteComparisonOp {
-    fn from(value: u8) -> Self {
-        // unsafe but less of a headache, just make sure the value is in range
-        unsafe { std::mem::transmute(value) }
+impl TryFrom<usize> for ByteComparisonOp {
+    type Error = usize;
+
+    /// The comparison a `Compare` operand stands for, or the operand back if it is none.
+    fn try_from(operand: usize) -> Result<Self, Self::Error> {
+        match operand {
+            0 => Ok(ByteComparisonOp::Equal),
+            1 => Ok(ByteComparisonOp::NotEqual),
+            2 => Ok(ByteComparisonOp::Less),
+            3 => Ok(ByteComparisonOp::LessEqual),
+            4 => Ok(ByteComparisonOp::Greater),
+            5 => Ok(ByteComparisonOp::GreaterEqual),
+            _ => Err(operand),
+        }
     }
 }
 
@@ -88,8 +225,65 @@ impl OpIndex {
         }
     }
 
+    /// Appends the operation's encoding to `code`: the operation byte, then the operand (if the
+    /// operation has one) as an unsigned LEB128 varint, seven bits a byte, low bits first.
+    pub fn encode(&self, code: &mut Vec<u8>) {
+        code.push(self.operation as u8);
+        if !self.operation.has_operand() {
+            debug_assert_eq!(self.operand, 0, "{:?} takes no operand", self.operation);
+            return;
+        }
+        let mut operand = self.operand;
+        while operand >= 0x80 {
+            code.push(operand as u8 | 0x80);
+            operand >>= 7;
+        }
+        code.push(operand as u8);
+    }
+
+    /// How many bytes `encode` appends.
+    pub fn encoded_len(&self) -> usize {
+        if !self.operation.has_operand() {
+            return 1;
+        }
+        let significant_bits = usize::BITS - self.operand.leading_zeros();
+        1 + (significant_bits.max(1) as usize).div_ceil(7)
+    }
+
+    /// Reads the operation encoded at `ip` and moves `ip` past it. `None` if the bytes there
+    /// are not an operation or are cut off.
+    #[inline]
+    pub fn decode(code: &[u8], ip: &mut usize) -> Option<Self> {
+        let operation = ByteOp::from_byte(*code.get(*ip)?)?;
+        let mut position = *ip + 1;
+        let mut operand = 0usize;
+        if operation.has_operand() {
+            let mut shift = 0;
+            loop {
+                let byte = *code.get(position)?;
+                position += 1;
+                if shift >= usize::BITS {
+                    return None;
+                }
+                operand |= ((byte & 0x7F) as usize) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+        }
+        *ip = position;
+        Some(Self { operation, operand })
+    }
+
+    /// The operation's encoded bytes, as they appear in the code.
     pub fn hex(&self) -> String {
-        format!("0x{:02X} {}", self.operation as u8, self.operand)
+        let mut code = Vec::with_capacity(self.encoded_len());
+        self.encode(&mut code);
+        code.iter()
+            .map(|byte| format!("0x{:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(" ")
     }
 }
 
@@ -0,0 +1,452 @@
+use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::OpIndex;
+use crate::compiler::code_object::CodeObject;
+use crate::compiler::verifier::{self, VerifyError};
+use crate::runtime::value::{FunctionValue, Value};
+use indexmap::IndexMap;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+// Layout of a `.shrc` file, integers as unsigned LEB128 varints unless noted:
+//   magic `SHRC`, format version (u16, little endian), then the top-level code object:
+//   id, name, file, code, constants, variables, constant lookup, variable lookup, line table
+// Strings and byte runs are a length followed by the bytes, sequences a count followed by
+// the items. Constants are a tag byte and their value; functions, methods and classes carry
+// their code object inline, so nested code follows its parent.
+
+pub const BYTECODE_EXTENSION: &str = "shrc";
+const MAGIC: &[u8; 4] = b"SHRC";
+// bumped whenever the layout or the operation encoding changes
+const FORMAT_VERSION: u16 = 1;
+// deeper nesting than any script has is taken for a corrupt file, not recursed into
+const MAX_NESTING: usize = 256;
+
+const TAG_INT: u8 = 0;
+const TAG_FLOAT: u8 = 1;
+const TAG_BOOL: u8 = 2;
+const TAG_STRING: u8 = 3;
+const TAG_LIST: u8 = 4;
+const TAG_TUPLE: u8 = 5;
+const TAG_MAP: u8 = 6;
+const TAG_FUNCTION: u8 = 7;
+const TAG_METHOD: u8 = 8;
+const TAG_CLASS: u8 = 9;
+
+/// Why a `.shrc` file could not be loaded.
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    /// The file does not start with the `.shrc` magic bytes.
+    NotBytecode,
+    /// The file was written in another version of the format.
+    UnsupportedVersion(u16),
+    /// The contents are cut off or inconsistent at `offset` into the file.
+    Corrupt { offset: usize, reason: String },
+    /// The file is well formed, but its code would not run safely.
+    Unverified(VerifyError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::NotBytecode => write!(f, "not a compiled script"),
+            LoadError::UnsupportedVersion(version) => write!(
+                f,
+                "compiled with format version {}, expected {}",
+                version, FORMAT_VERSION
+            ),
+            LoadError::Corrupt { offset, reason } => {
+                write!(f, "corrupt at byte {}: {}", offset, reason)
+            }
+            LoadError::Unverified(err) => write!(f, "invalid code in {}", err),
+        }
+    }
+}
+
+/// Serializes a compiled top-level code object, and everything nested in it, to `.shrc`.
+pub fn save(code_object: &CodeObject) -> Vec<u8> {
+    let mut writer = Writer { bytes: Vec::new() };
+    writer.bytes.extend_from_slice(MAGIC);
+    writer
+        .bytes
+        .extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    writer.code_object(code_object);
+    writer.bytes
+}
+
+/// Reads a code object saved by `save` and verifies its code. The code objects get fresh ids,
+/// so loaded code never shares scopes with code compiled or loaded elsewhere.
+pub fn load(bytes: &[u8]) -> Result<CodeObject, LoadError> {
+    if !bytes.starts_with(MAGIC) {
+        return Err(LoadError::NotBytecode);
+    }
+    let mut reader = Reader {
+        bytes,
+        position: MAGIC.len(),
+        ids: HashMap::new(),
+        depth: 0,
+    };
+    let version = u16::from_le_bytes([reader.byte()?, reader.byte()?]);
+    if version != FORMAT_VERSION {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+    let code_object = reader.code_object()?;
+    if reader.position != bytes.len() {
+        return Err(reader.corrupt("trailing bytes after the code"));
+    }
+    verifier::verify(&code_object).map_err(LoadError::Unverified)?;
+    Ok(code_object)
+}
+
+struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    fn uint(&mut self, mut value: usize) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.uint(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+
+    fn code_object(&mut self, code_object: &CodeObject) {
+        self.uint(code_object.id);
+        self.str(&code_object.name);
+        self.str(&code_object.file);
+        self.uint(code_object.code.len());
+        self.bytes.extend_from_slice(&code_object.code);
+        self.uint(code_object.constants.len());
+        for constant in code_object.constants.iter() {
+            self.constant(&constant.borrow());
+        }
+        self.uint(code_object.variables.len());
+        for variable in code_object.variables.iter() {
+            self.str(variable);
+        }
+        // sorted, so compiling the same source always writes the same file
+        let mut constant_lookup: Vec<_> = code_object.constant_index_lookup.iter().collect();
+        constant_lookup.sort();
+        self.uint(constant_lookup.len());
+        for (node_id, index) in constant_lookup {
+            self.uint(*node_id);
+            self.uint(*index);
+        }
+        let mut variable_lookup: Vec<_> = code_object.variable_index_lookup.iter().collect();
+        variable_lookup.sort_by_key(|(name, index)| (**index, name.as_str()));
+        self.uint(variable_lookup.len());
+        for (name, index) in variable_lookup {
+            self.str(name);
+            self.uint(*index);
+        }
+        self.uint(code_object.line_table.len());
+        for (start, line) in code_object.line_table.iter() {
+            self.uint(*start);
+            self.uint(*line);
+        }
+    }
+
+    fn constant(&mut self, constant: &Value) {
+        match constant {
+            Value::Int(value) => {
+                self.bytes.push(TAG_INT);
+                self.bytes.extend_from_slice(&value.0.to_le_bytes());
+            }
+            Value::Float(value) => {
+                self.bytes.push(TAG_FLOAT);
+                self.bytes.extend_from_slice(&value.0.to_le_bytes());
+            }
+            Value::Bool(value) => {
+                self.bytes.push(TAG_BOOL);
+                self.bytes.push(value.0 as u8);
+            }
+            Value::String(value) => {
+                self.bytes.push(TAG_STRING);
+                self.str(&value.0);
+            }
+            Value::List(list) => {
+                self.bytes.push(TAG_LIST);
+                self.uint(list.elements.len());
+                list.elements
+                    .iter()
+                    .for_each(|element| self.constant(&element.borrow()));
+            }
+            Value::Tuple(tuple) => {
+                self.bytes.push(TAG_TUPLE);
+                self.uint(tuple.elements.len());
+                tuple
+                    .elements
+                    .iter()
+                    .for_each(|element| self.constant(&element.borrow()));
+            }
+            Value::Map(map) => {
+                self.bytes.push(TAG_MAP);
+                self.uint(map.properties.len());
+                for (key, value) in map.properties.iter() {
+                    self.constant(key);
+                    self.constant(&value.borrow());
+                }
+            }
+            Value::Function(function) => {
+                self.bytes.push(TAG_FUNCTION);
+                self.function(function);
+            }
+            Value::Method(method) => {
+                self.bytes.push(TAG_METHOD);
+                self.function(&method.function);
+            }
+            Value::Class(class) => {
+                self.bytes.push(TAG_CLASS);
+                self.code_object(&class.body);
+            }
+            _ => panic!("Unexpected constant: {:?}", constant),
+        }
+    }
+
+    fn function(&mut self, function: &FunctionValue) {
+        self.uint(function.parameters.len());
+        for parameter in function.parameters.iter() {
+            self.str(parameter);
+        }
+        self.code_object(&function.body);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    ids: HashMap<usize, usize>, // saved code object id -> id of the loaded code object
+    depth: usize,               // code objects and constants being read, outermost first
+}
+
+impl Reader<'_> {
+    fn corrupt(&self, reason: &str) -> LoadError {
+        LoadError::Corrupt {
+            offset: self.position,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, LoadError> {
+        let byte = *self
+            .bytes
+            .get(self.position)
+            .ok_or_else(|| self.corrupt("unexpected end of file"))?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], LoadError> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| self.corrupt("unexpected end of file"))?;
+        let taken = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(taken)
+    }
+
+    fn uint(&mut self) -> Result<usize, LoadError> {
+        let mut value = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.byte()?;
+            if shift >= usize::BITS {
+                return Err(self.corrupt("integer out of range"));
+            }
+            value |= ((byte & 0x7F) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn str(&mut self) -> Result<String, LoadError> {
+        let len = self.uint()?;
+        let bytes = self.take(len)?.to_vec();
+        String::from_utf8(bytes).map_err(|_| self.corrupt("string is not UTF-8"))
+    }
+
+    fn index(&mut self, len: usize, what: &str) -> Result<usize, LoadError> {
+        let index = self.uint()?;
+        if index >= len {
+            return Err(self.corrupt(&format!("{} index {} out of range", what, index)));
+        }
+        Ok(index)
+    }
+
+    fn nested<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, LoadError>,
+    ) -> Result<T, LoadError> {
+        if self.depth == MAX_NESTING {
+            return Err(self.corrupt("nested too deeply"));
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn code_object(&mut self) -> Result<CodeObject, LoadError> {
+        self.nested(|reader| {
+            let saved_id = reader.uint()?;
+            let name = reader.str()?;
+            let file = Rc::from(reader.str()?);
+            let mut code_object = CodeObject::new(name, file);
+            // nested code loads the scopes of the code it is nested in, saved before it
+            reader.ids.insert(saved_id, code_object.id);
+
+            let code_len = reader.uint()?;
+            let code_start = reader.position;
+            let code = reader.take(code_len)?.to_vec();
+            for _ in 0..reader.uint()? {
+                let constant = reader.constant()?;
+                code_object.constants.push(Rc::new(RefCell::new(constant)));
+            }
+            for _ in 0..reader.uint()? {
+                code_object.variables.push(reader.str()?);
+            }
+            for _ in 0..reader.uint()? {
+                let node_id = reader.uint()?;
+                let index = reader.index(code_object.constants.len(), "constant")?;
+                code_object.constant_index_lookup.insert(node_id, index);
+            }
+            for _ in 0..reader.uint()? {
+                let name = reader.str()?;
+                let index = reader.index(code_object.variables.len(), "variable")?;
+                code_object.variable_index_lookup.insert(name, index);
+            }
+            for _ in 0..reader.uint()? {
+                let start = reader.uint()?;
+                let line = reader.uint()?;
+                code_object.line_table.push((start, line));
+            }
+            reader.relink(&mut code_object, &code, code_start)?;
+            Ok(code_object)
+        })
+    }
+
+    /// Decodes the saved code into the code object's operations, pointing its scope loads at
+    /// the loaded code objects, and assembles them again.
+    fn relink(
+        &self,
+        code_object: &mut CodeObject,
+        code: &[u8],
+        code_start: usize,
+    ) -> Result<(), LoadError> {
+        let corrupt = |offset: usize, reason: &str| LoadError::Corrupt {
+            offset: code_start + offset,
+            reason: reason.to_string(),
+        };
+        // operation index of each offset an operation starts at, the end included
+        let mut indexes = HashMap::new();
+        let mut starts = Vec::new();
+        let mut ip = 0;
+        while ip < code.len() {
+            indexes.insert(ip, code_object.operations.len());
+            starts.push(ip);
+            let offset = ip;
+            let mut op = OpIndex::decode(code, &mut ip)
+                .ok_or_else(|| corrupt(offset, "invalid operation"))?;
+            if matches!(op.operation, ByteOp::LoadScope) {
+                op.operand = *self
+                    .ids
+                    .get(&op.operand)
+                    .ok_or_else(|| corrupt(offset, "scope of unknown code"))?;
+            }
+            code_object.operations.push(op);
+        }
+        indexes.insert(code.len(), code_object.operations.len());
+
+        for (op, offset) in code_object.operations.iter_mut().zip(starts) {
+            if op.operation.is_jump() {
+                op.operand = *indexes
+                    .get(&op.operand)
+                    .ok_or_else(|| corrupt(offset, "jump into the middle of an operation"))?;
+            }
+        }
+        // the line table was read last, errors in it point at its end
+        let mut previous_start = None;
+        for (start, _) in code_object.line_table.iter_mut() {
+            if previous_start.is_some_and(|previous| previous >= *start) {
+                return Err(self.corrupt("line table out of order"));
+            }
+            previous_start = Some(*start);
+            *start = *indexes
+                .get(start)
+                .ok_or_else(|| self.corrupt("line table entry inside an operation"))?;
+        }
+        code_object.assemble();
+        Ok(())
+    }
+
+    fn constant(&mut self) -> Result<Value, LoadError> {
+        self.nested(|reader| {
+            let tag = reader.byte()?;
+            let value = match tag {
+                TAG_INT => Value::int(i64::from_le_bytes(reader.take(8)?.try_into().unwrap())),
+                TAG_FLOAT => Value::float(f64::from_le_bytes(reader.take(8)?.try_into().unwrap())),
+                TAG_BOOL => match reader.byte()? {
+                    0 => Value::bool(false),
+                    1 => Value::bool(true),
+                    _ => return Err(reader.corrupt("invalid bool")),
+                },
+                TAG_STRING => Value::string(reader.str()?),
+                TAG_LIST => Value::list(reader.elements()?),
+                TAG_TUPLE => Value::tuple(reader.elements()?),
+                TAG_MAP => {
+                    let mut properties = IndexMap::new();
+                    for _ in 0..reader.uint()? {
+                        let key = reader.constant()?;
+                        let value = reader.constant()?;
+                        properties.insert(key, Rc::new(RefCell::new(value)));
+                    }
+                    Value::map(properties)
+                }
+                TAG_FUNCTION => Value::Function(reader.function()?),
+                TAG_METHOD => Value::method(reader.function()?, None),
+                TAG_CLASS => Value::class(None, Rc::new(reader.code_object()?)),
+                _ => return Err(reader.corrupt(&format!("unknown constant tag {}", tag))),
+            };
+            Ok(value)
+        })
+    }
+
+    fn elements(&mut self) -> Result<Vec<Rc<RefCell<Value>>>, LoadError> {
+        let mut elements = Vec::new();
+        for _ in 0..self.uint()? {
+            elements.push(Rc::new(RefCell::new(self.constant()?)));
+        }
+        Ok(elements)
+    }
+
+    fn function(&mut self) -> Result<FunctionValue, LoadError> {
+        let mut parameters = Vec::new();
+        for _ in 0..self.uint()? {
+            parameters.push(self.str()?);
+        }
+        let body = self.code_object()?;
+        // calls store the arguments into the body's variables, the body was read last so the
+        // error points at its end
+        if let Some(parameter) = parameters
+            .iter()
+            .find(|parameter| !body.variable_index_lookup.contains_key(*parameter))
+        {
+            return Err(self.corrupt(&format!(
+                "parameter {} is not a variable of {}",
+                parameter, body.name
+            )));
+        }
+        Ok(FunctionValue::new(parameters, Rc::new(body)))
+    }
+}
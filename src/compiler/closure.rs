@@ -7,60 +7,144 @@ use crate::parser::nodes::{IfNode, WhileNode};
 use std::cell::RefCell;
 use std::rc::Rc;
 
-fn make_closure_body(compiler: &mut Compiler, body: Vec<ExprNode>) {
+/// Whether a statement leaves a value on the stack: a call statement discards its own result,
+/// and branching statements leave one only when some branch ends in a value.
+fn leaves_value(statement: &ExprNode) -> bool {
+    match statement {
+        ExprNode::Identity(identity) => !matches!(identity.address.as_slice(), [ExprNode::Call(_)]),
+        ExprNode::If(if_node) => branches_leave_value([&if_node.then_body, &if_node.else_body]),
+        ExprNode::Match(match_node) => {
+            branches_leave_value(match_node.arms.iter().map(|arm| &arm.body))
+        }
+        _ => statement.produces_value(),
+    }
+}
+
+pub(crate) fn branches_leave_value<'a>(
+    branches: impl IntoIterator<Item = &'a Vec<ExprNode>>,
+) -> bool {
+    branches
+        .into_iter()
+        .any(|body| body.last().is_some_and(leaves_value))
+}
+
+/// Compiles a statement whose value nobody uses, popping it if it leaves one, so every path
+/// through a loop or branch leaves the stack as deep as it found it.
+fn discarded_statement(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    statement: ExprNode,
+) {
+    let pop = leaves_value(&statement);
+    compiler.compile_expr(statement, &CompileContext::Normal);
+    if pop {
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::without_op(ByteOp::Pop),
+        );
+    }
+}
+
+pub(crate) fn make_closure_body(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    body: Vec<ExprNode>,
+) {
     for ast_node in body.into_iter() {
-        compiler.compile_expr(ast_node, &CompileContext::Normal);
+        discarded_statement(compiler, &code_object, ast_node);
     }
 }
 
+pub(crate) fn make_value_closure_body(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    body: Vec<ExprNode>,
+    context: &CompileContext,
+) {
+    // the last expression is the closure's value, null if it doesn't leave one
+    let produces_value = body.last().is_some_and(|last| last.produces_value());
+    let last_index = body.len().saturating_sub(1);
+    for (i, ast_node) in body.into_iter().enumerate() {
+        if i == last_index {
+            compiler.compile_expr(ast_node, context);
+        } else {
+            discarded_statement(compiler, &code_object, ast_node);
+        }
+    }
+    if !produces_value {
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::without_op(ByteOp::LoadNull),
+        );
+    }
+}
+
+pub(crate) fn push_jump_placeholder(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    op: ByteOp,
+) -> usize {
+    let mut_code_obj = &mut *code_object.borrow_mut();
+    let idx = mut_code_obj.operations.len();
+    compiler.push_op(mut_code_obj, OpIndex::without_op(op));
+    idx
+}
+
+pub(crate) fn patch_jump_to_here(code_object: &Rc<RefCell<CodeObject>>, jump_op_index: usize) {
+    let mut_code_obj = &mut *code_object.borrow_mut();
+    mut_code_obj.operations[jump_op_index].operand = mut_code_obj.operations.len();
+}
+
 pub(crate) fn while_closure(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     while_node: WhileNode,
 ) {
-    compiler.compile_expr(*while_node.condition, &CompileContext::Normal);
-    let loop_body_start_index = compiler.ip;
-    let pop_jump_op_index = {
-        let mut_code_obj = &mut *code_object.borrow_mut();
-        let idx = mut_code_obj.operations.len();
-        compiler.push_op(mut_code_obj, OpIndex::without_op(ByteOp::PopJumpIfFalse));
-        idx
-    };
-    make_closure_body(compiler, while_node.body);
+    let loop_start_index = code_object.borrow().operations.len();
+    compiler.compile_expr(*while_node.condition, &CompileContext::Binary);
+    let pop_jump_op_index = push_jump_placeholder(compiler, &code_object, ByteOp::PopJumpIfFalse);
+    make_closure_body(compiler, code_object.clone(), while_node.body);
 
-    let mut_code_obj = &mut *code_object.borrow_mut();
     compiler.push_op(
-        mut_code_obj,
-        OpIndex::with_op(ByteOp::Jump, loop_body_start_index),
+        &mut code_object.borrow_mut(),
+        OpIndex::with_op(ByteOp::Jump, loop_start_index),
     );
-    mut_code_obj.operations[pop_jump_op_index].operand = compiler.ip;
+    patch_jump_to_here(&code_object, pop_jump_op_index);
 }
 
 pub(crate) fn if_closure(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     if_node: IfNode,
+    context: &CompileContext,
 ) {
-    compiler.compile_expr(*if_node.condition, &CompileContext::Normal);
+    compiler.compile_expr(*if_node.condition, &CompileContext::Binary);
+    let pop_jump_false_op_index =
+        push_jump_placeholder(compiler, &code_object, ByteOp::PopJumpIfFalse);
 
-    let pop_jump_false_op_index = {
-        let mut_code_obj = &mut *code_object.borrow_mut();
-        let idx = mut_code_obj.operations.len();
-        compiler.push_op(mut_code_obj, OpIndex::without_op(ByteOp::PopJumpIfFalse));
-        idx
+    // as a value both branches leave exactly one value, a missing else branch yields null; so
+    // do statements with a branch ending in a value, which is their result
+    let wants_value =
+        context.wants_value() || branches_leave_value([&if_node.then_body, &if_node.else_body]);
+    let context = if context.wants_value() {
+        context
+    } else {
+        &CompileContext::Result
     };
+    if wants_value {
+        make_value_closure_body(compiler, code_object.clone(), if_node.then_body, context);
+    } else {
+        make_closure_body(compiler, code_object.clone(), if_node.then_body);
+    }
 
-    make_closure_body(compiler, if_node.then_body);
-
-    let pop_jump_true_op_index = {
-        let mut_code_obj = &mut *code_object.borrow_mut();
-        let idx = mut_code_obj.operations.len();
-        compiler.push_op(mut_code_obj, OpIndex::without_op(ByteOp::Jump));
-        mut_code_obj.operations[pop_jump_false_op_index].operand = compiler.ip;
-        idx
-    };
+    let jump_end_op_index = push_jump_placeholder(compiler, &code_object, ByteOp::Jump);
+    patch_jump_to_here(&code_object, pop_jump_false_op_index);
 
-    make_closure_body(compiler, if_node.else_body);
+    if wants_value {
+        make_value_closure_body(compiler, code_object.clone(), if_node.else_body, context);
+    } else {
+        make_closure_body(compiler, code_object.clone(), if_node.else_body);
+    }
 
-    code_object.borrow_mut().operations[pop_jump_true_op_index].operand = compiler.ip;
+    patch_jump_to_here(&code_object, jump_end_op_index);
 }
@@ -11,18 +11,33 @@ use std::rc::Rc;
 #[derive(Debug, Clone)]
 pub struct CodeObject {
     pub id: usize,
-    pub operations: Vec<OpIndex>,
+    pub name: String, // function or class name, `<module>` for a source file's top level
+    pub file: Rc<str>,
+    pub operations: Vec<OpIndex>, // while compiling, `assemble` packs them into `code`
+    pub code: Vec<u8>,            // the encoded operations the runtime executes
     pub constants: Vec<Rc<RefCell<Value>>>, // these are never mutated, RefCell for uniformity
     pub variables: Vec<String>,
     pub constant_index_lookup: HashMap<usize, usize>, // constant ExprNode id -> constant index
     pub variable_index_lookup: HashMap<String, usize>, // variable name -> variable index
+    pub line_table: Vec<(usize, usize)>, // (first operation offset, source line), ascending
 }
 
 impl CodeObject {
-    pub fn from_function(function_node: &FunctionNode) -> Self {
+    pub fn new(name: String, file: Rc<str>) -> Self {
+        Self {
+            name,
+            file,
+            ..Self::default()
+        }
+    }
+
+    pub fn from_function(function_node: &FunctionNode, file: Rc<str>) -> Self {
         Self {
             id: CODE_OBJECT_ID.next(),
+            name: function_node.name.clone(),
+            file,
             operations: Vec::new(),
+            code: Vec::new(),
             constants: Vec::new(),
             variables: function_node.arguments.clone(),
             constant_index_lookup: HashMap::new(),
@@ -32,27 +47,99 @@ impl CodeObject {
                 .enumerate()
                 .map(|(i, v)| (v.clone(), i))
                 .collect(),
+            line_table: Vec::new(),
+        }
+    }
+
+    /// Encodes the compiled operations into `code`, turning jump operands and the line table
+    /// from operation indexes into byte offsets.
+    pub(crate) fn assemble(&mut self) {
+        let operations = std::mem::take(&mut self.operations);
+        // a jump's width depends on the offset it resolves to, which depends on the widths
+        // before it; offsets only grow from all zeros, so they settle after a few passes
+        let mut offsets = vec![0; operations.len() + 1];
+        loop {
+            let mut offset = 0;
+            let mut settled = true;
+            for (i, op) in operations.iter().enumerate() {
+                settled &= offsets[i] == offset;
+                offsets[i] = offset;
+                offset += Self::relocated(op, &offsets).encoded_len();
+            }
+            settled &= offsets[operations.len()] == offset;
+            offsets[operations.len()] = offset;
+            if settled {
+                break;
+            }
+        }
+
+        let mut code = Vec::with_capacity(offsets[operations.len()]);
+        for op in operations.iter() {
+            Self::relocated(op, &offsets).encode(&mut code);
+        }
+        self.code = code;
+        for (start, _) in self.line_table.iter_mut() {
+            *start = offsets[*start];
+        }
+        self.line_table.shrink_to_fit();
+    }
+
+    fn relocated(op: &OpIndex, offsets: &[usize]) -> OpIndex {
+        if op.operation.is_jump() {
+            OpIndex::with_op(op.operation, offsets[op.operand])
+        } else {
+            *op
+        }
+    }
+
+    /// The encoded operations, each with the offset it starts at.
+    pub fn instructions(&self) -> impl Iterator<Item = (usize, OpIndex)> + '_ {
+        let mut ip = 0;
+        std::iter::from_fn(move || {
+            let offset = ip;
+            OpIndex::decode(&self.code, &mut ip).map(|op| (offset, op))
+        })
+    }
+
+    /// Attributes the operations from `ip` on to `line`, up to the next line change.
+    pub(crate) fn mark_line(&mut self, ip: usize, line: usize) {
+        if self.line_table.last().map(|(_, last_line)| *last_line) != Some(line) {
+            self.line_table.push((ip, line));
         }
     }
+
+    /// The source line the operation at `ip` was compiled from.
+    pub fn line_at(&self, ip: usize) -> Option<usize> {
+        let entry = self.line_table.partition_point(|(start, _)| *start <= ip);
+        entry.checked_sub(1).map(|entry| self.line_table[entry].1)
+    }
+
+    /// Whether functions or classes are defined in this code, their nonlocal variables may
+    /// then live in its frames.
+    pub fn defines_scopes(&self) -> bool {
+        self.constants.iter().any(|constant| {
+            matches!(
+                &*constant.borrow(),
+                Value::Function(_) | Value::Method(_) | Value::Class(_)
+            )
+        })
+    }
 }
 
+// code objects are compared by identity, so functions and classes hash by the code they run
 impl Hash for CodeObject {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        unreachable!("Shouldn't be hashing a code object")
+        self.id.hash(state)
     }
 }
 
 impl PartialEq for CodeObject {
     fn eq(&self, other: &Self) -> bool {
-        unreachable!("Shouldn't be comparing code objects")
+        self.id == other.id
     }
 }
 
-impl Eq for CodeObject {
-    fn assert_receiver_is_total_eq(&self) {
-        unreachable!("Shouldn't be comparing code objects")
-    }
-}
+impl Eq for CodeObject {}
 
 static CODE_OBJECT_ID: Counter = Counter::new();
 
@@ -60,11 +147,15 @@ impl Default for CodeObject {
     fn default() -> Self {
         Self {
             id: CODE_OBJECT_ID.next(),
+            name: String::from("<module>"),
+            file: Rc::from(""),
             operations: Vec::new(),
+            code: Vec::new(),
             constants: Vec::new(),
             variables: Vec::new(),
             constant_index_lookup: HashMap::new(),
             variable_index_lookup: HashMap::new(),
+            line_table: Vec::new(),
         }
     }
 }
@@ -1,13 +1,20 @@
 use crate::compiler::byte_operations::*;
 use crate::compiler::closure::*;
 use crate::compiler::code_object::CodeObject;
+use crate::compiler::fold::fold_constants;
+use crate::compiler::import::*;
 use crate::compiler::load::*;
 use crate::compiler::make::*;
 use crate::compiler::op::*;
-use crate::parser::ExprNode;
+use crate::compiler::pattern::*;
+use crate::compiler::peephole;
+use crate::lexer::{Lexer, TokenKind};
 use crate::parser::traits::HasId;
+use crate::parser::{ExprNode, Parser};
 use crate::runtime::value::Value;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::Path;
 use std::rc::Rc;
 
 #[derive(Debug)]
@@ -19,28 +26,92 @@ pub enum CompileContext {
     Identity,
     Binary,
     Return,
+    /// The value a branching statement leaves as its result.
+    Result,
     Normal,
 }
 
+impl CompileContext {
+    /// Whether the compiled expression's value is consumed by the enclosing expression.
+    pub(crate) fn wants_value(&self) -> bool {
+        matches!(
+            self,
+            CompileContext::Assignment
+                | CompileContext::Argument
+                | CompileContext::Identity
+                | CompileContext::Binary
+                | CompileContext::Return
+                | CompileContext::Result
+        )
+    }
+}
+
+/// How much the compiler rewrites the code it generates. Every level runs a program the same
+/// way; higher ones just run fewer operations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptimizationLevel {
+    /// Operations exactly as the source spells them out.
+    None,
+    /// Operations on literals folded in the AST.
+    Fold,
+    /// Folding, then the peephole pass over each code object's operations.
+    #[default]
+    Peephole,
+}
+
+impl OptimizationLevel {
+    pub fn from_level(level: u8) -> Option<Self> {
+        match level {
+            0 => Some(OptimizationLevel::None),
+            1 => Some(OptimizationLevel::Fold),
+            2 => Some(OptimizationLevel::Peephole),
+            _ => None,
+        }
+    }
+}
+
 pub struct Compiler {
     pub(crate) ip: usize,
     pub(crate) scope_stack: Vec<Rc<RefCell<CodeObject>>>,
+    pub(crate) file: Rc<str>,
+    lines: HashMap<usize, usize>, // statement node id -> source line, from the parser
+    line: usize,                  // line of the statement being compiled
+    optimization: OptimizationLevel,
 }
 
 impl Compiler {
-    pub fn new() -> Self {
+    pub fn new(
+        file: Rc<str>,
+        lines: HashMap<usize, usize>,
+        optimization: OptimizationLevel,
+    ) -> Self {
         Self {
             ip: 0,
             scope_stack: Vec::new(),
+            file,
+            lines,
+            line: 0,
+            optimization,
         }
     }
 
     pub(crate) fn push_op(&mut self, code_object: &mut CodeObject, op: OpIndex) {
+        code_object.mark_line(code_object.operations.len(), self.line);
         code_object.operations.push(op);
         self.ip += 1;
     }
 
     pub(crate) fn compile_expr(&mut self, expr: ExprNode, context: &CompileContext) {
+        // operations belong to the innermost statement they were compiled for
+        let enclosing_line = self.line;
+        if let Some(line) = self.lines.get(&expr.id()) {
+            self.line = *line;
+        }
+        self.compile_node(expr, context);
+        self.line = enclosing_line;
+    }
+
+    fn compile_node(&mut self, expr: ExprNode, context: &CompileContext) {
         let code_object_ref = self.scope_stack.last().unwrap().clone();
         match expr {
             ExprNode::Int(_) | ExprNode::Float(_) | ExprNode::Bool(_) | ExprNode::String(_) => {
@@ -53,6 +124,8 @@ impl Compiler {
             }
             ExprNode::Map(map) => make_map(self, code_object_ref, map),
             ExprNode::List(list) => make_list(self, code_object_ref, list),
+            ExprNode::Tuple(tuple) => make_tuple(self, code_object_ref, tuple),
+            ExprNode::Slice(slice) => make_slice(self, code_object_ref, slice),
             ExprNode::Function(function_node) => {
                 if matches!(context, CompileContext::Class)
                     && function_node
@@ -70,11 +143,19 @@ impl Compiler {
             ExprNode::Identity(identity_node) => {
                 identity(self, code_object_ref, identity_node, context)
             }
+            ExprNode::Reference(reference_node) => reference(self, code_object_ref, reference_node),
+            ExprNode::Deref(deref_node) => dereference(self, code_object_ref, deref_node),
             ExprNode::Call(call_node) => call(self, code_object_ref, call_node, context),
             ExprNode::Assign(assign_node) => assign(self, code_object_ref, assign_node, context),
+            ExprNode::Destructure(destructure_node) => {
+                destructure(self, code_object_ref, destructure_node)
+            }
             ExprNode::Binary(binary_node) => binary(self, code_object_ref, binary_node, context),
-            ExprNode::If(if_node) => if_closure(self, code_object_ref, if_node),
+            ExprNode::If(if_node) => if_closure(self, code_object_ref, if_node, context),
             ExprNode::While(while_node) => while_closure(self, code_object_ref, while_node),
+            ExprNode::Match(match_node) => {
+                match_closure(self, code_object_ref, match_node, context)
+            }
             ExprNode::Comparison(comparison_node) => {
                 comparison(self, code_object_ref, comparison_node)
             }
@@ -82,6 +163,7 @@ impl Compiler {
             ExprNode::Return(return_node) => {
                 return_value(self, code_object_ref, return_node, context)
             }
+            ExprNode::Import(import_node) => import_module(self, code_object_ref, import_node),
             ExprNode::Null => self.push_op(
                 &mut *code_object_ref.borrow_mut(),
                 OpIndex::without_op(ByteOp::LoadNull),
@@ -96,16 +178,43 @@ impl Compiler {
         context: &CompileContext,
         code_object: Rc<RefCell<CodeObject>>,
     ) {
-        self.scope_stack.push(code_object);
+        self.scope_stack.push(code_object.clone());
         for ast_node in ast.into_iter() {
             self.compile_expr(ast_node, context);
         }
         self.scope_stack.pop();
+        let mut code_object = code_object.borrow_mut();
+        if self.optimization >= OptimizationLevel::Peephole {
+            peephole::optimize(&mut code_object);
+        }
+        code_object.assemble();
     }
 
-    pub fn compile(&mut self, ast: Vec<ExprNode>, context: &CompileContext) -> CodeObject {
-        let code_object = Rc::new(RefCell::new(CodeObject::default()));
+    pub fn compile(
+        &mut self,
+        name: String,
+        ast: Vec<ExprNode>,
+        context: &CompileContext,
+    ) -> CodeObject {
+        let code_object = Rc::new(RefCell::new(CodeObject::new(name, self.file.clone())));
         self.compile_into(ast, context, code_object.clone());
         Rc::try_unwrap(code_object).unwrap().into_inner()
     }
 }
+
+/// Lexes, parses and compiles a whole source file into its top-level code object. `file` is
+/// what tracebacks name the source by.
+pub fn compile_source(source: &str, file: &Path, optimization: OptimizationLevel) -> CodeObject {
+    let mut lex = Lexer::new(source);
+    let mut parser = Parser::new(&mut lex);
+    let mut ast = parser.parse(TokenKind::EOF);
+    if optimization >= OptimizationLevel::Fold {
+        fold_constants(&mut ast);
+    }
+    let file = Rc::from(file.to_string_lossy().as_ref());
+    Compiler::new(file, parser.into_lines(), optimization).compile(
+        String::from("<module>"),
+        ast,
+        &CompileContext::Normal,
+    )
+}
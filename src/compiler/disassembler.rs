@@ -0,0 +1,153 @@
+use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::{ByteComparisonOp, OpIndex};
+use crate::compiler::code_object::CodeObject;
+use crate::runtime::builtins::BUILTINS;
+use crate::runtime::value::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+
+/// A code object whose frames scope loads can name.
+struct Scope {
+    id: usize,
+    name: String,
+    variables: Vec<String>,
+}
+
+impl Scope {
+    fn of(code_object: &CodeObject) -> Self {
+        Self {
+            id: code_object.id,
+            name: code_object.name.clone(),
+            variables: code_object.variables.clone(),
+        }
+    }
+}
+
+/// Lists the operations of the code object, then of every code object nested in its
+/// constants, with operands resolved to the constants, variables, scopes, comparisons and
+/// jump targets they stand for.
+pub fn disassemble(code_object: &CodeObject) -> String {
+    disassemble_in(code_object, &[])
+}
+
+/// Like `disassemble`, for code nested in `scopes`, which its scope loads may name.
+pub fn disassemble_in(code_object: &CodeObject, scopes: &[&CodeObject]) -> String {
+    let mut out = String::new();
+    let mut enclosing = scopes.iter().map(|scope| Scope::of(scope)).collect();
+    write_code_object(&mut out, code_object, &mut enclosing);
+    out
+}
+
+fn write_code_object(out: &mut String, code_object: &CodeObject, enclosing: &mut Vec<Scope>) {
+    enclosing.push(Scope::of(code_object));
+    let instructions: Vec<(usize, OpIndex)> = code_object.instructions().collect();
+    let mut targets: Vec<usize> = instructions
+        .iter()
+        .filter(|(_, op)| op.operation.is_jump())
+        .map(|(_, op)| op.operand)
+        .collect();
+    targets.sort_unstable();
+    targets.dedup();
+    let labels: HashMap<usize, usize> = targets
+        .into_iter()
+        .enumerate()
+        .map(|(label, target)| (target, label))
+        .collect();
+
+    writeln!(
+        out,
+        "Disassembly of {} ({}):",
+        code_object.name, code_object.file
+    )
+    .unwrap();
+    let mut lines = code_object.line_table.iter().peekable();
+    // the scope the previous operation loaded, nonlocal loads read from it
+    let mut scope: Option<&Scope> = None;
+    for (offset, op) in instructions.iter() {
+        if let Some(label) = labels.get(offset) {
+            writeln!(out, "L{}:", label).unwrap();
+        }
+        let line = lines
+            .next_if(|(start, _)| start == offset)
+            .map(|(_, line)| line.to_string())
+            .unwrap_or_default();
+        let name = format!("{:?}", op.operation);
+        if op.operation.has_operand() {
+            write!(out, "{:>5} {:>6}  {:<24}{}", line, offset, name, op.operand).unwrap();
+            if let Some(detail) = detail(code_object, op, scope, enclosing, &labels) {
+                write!(out, " ({})", detail).unwrap();
+            }
+        } else {
+            write!(out, "{:>5} {:>6}  {}", line, offset, name).unwrap();
+        }
+        out.push('\n');
+        scope = match op.operation {
+            ByteOp::LoadScope => enclosing.iter().find(|scope| scope.id == op.operand),
+            _ => None,
+        };
+    }
+    if let Some(label) = labels.get(&code_object.code.len()) {
+        writeln!(out, "L{}:", label).unwrap();
+    }
+
+    let nested: Vec<Rc<CodeObject>> = code_object
+        .constants
+        .iter()
+        .filter_map(|constant| match &*constant.borrow() {
+            Value::Function(function) => Some(function.body.clone()),
+            Value::Method(method) => Some(method.function.body.clone()),
+            Value::Class(class) => Some(class.body.clone()),
+            _ => None,
+        })
+        .collect();
+    for body in nested {
+        out.push('\n');
+        write_code_object(out, &body, enclosing);
+    }
+    enclosing.pop();
+}
+
+/// What the operand stands for, when it is more than a count.
+fn detail(
+    code_object: &CodeObject,
+    op: &OpIndex,
+    scope: Option<&Scope>,
+    enclosing: &[Scope],
+    labels: &HashMap<usize, usize>,
+) -> Option<String> {
+    let index = op.operand;
+    match op.operation {
+        ByteOp::LoadConstant | ByteOp::Import => code_object
+            .constants
+            .get(index)
+            .map(|constant| describe_constant(&constant.borrow())),
+        ByteOp::LoadLocal | ByteOp::LoadReference | ByteOp::PreAssign | ByteOp::PostAssign => {
+            code_object.variables.get(index).cloned()
+        }
+        ByteOp::LoadNonlocal | ByteOp::LoadNonlocalReference => {
+            scope.and_then(|scope| scope.variables.get(index).cloned())
+        }
+        ByteOp::LoadScope => enclosing
+            .iter()
+            .find(|scope| scope.id == index)
+            .map(|scope| scope.name.clone()),
+        ByteOp::LoadBuiltin => BUILTINS.get(index).map(|(name, _)| name.to_string()),
+        ByteOp::Compare => ByteComparisonOp::try_from(index)
+            .ok()
+            .map(|comparison| format!("{:?}", comparison)),
+        ByteOp::Jump | ByteOp::PopJumpIfFalse => {
+            labels.get(&index).map(|label| format!("to L{}", label))
+        }
+        _ => None,
+    }
+}
+
+fn describe_constant(value: &Value) -> String {
+    match value {
+        Value::Function(function) => format!("<function {}>", function.body.name),
+        Value::Method(method) => format!("<method {}>", method.function.body.name),
+        Value::Class(class) => format!("<class {}>", class.body.name),
+        other => other.repr(),
+    }
+}
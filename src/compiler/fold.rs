@@ -0,0 +1,181 @@
+use crate::lexer::TokenKind;
+use crate::parser::ExprNode;
+use crate::parser::nodes::*;
+use crate::runtime::value::Value;
+use crate::runtime::value::traits::Binary;
+
+/// Replaces binary, comparison and logical operations on literals with their result, computed
+/// with the runtime's own `Binary` implementations. Operations that would fail (or panic) at
+/// runtime are left in place, so they still do when the code runs.
+pub(crate) fn fold_constants(ast: &mut [ExprNode]) {
+    ast.iter_mut().for_each(fold_expr);
+}
+
+fn fold_expr(expr: &mut ExprNode) {
+    match expr {
+        ExprNode::Binary(BinaryNode { left, right, .. })
+        | ExprNode::Comparison(ComparisonNode { left, right, .. })
+        | ExprNode::Logical(LogicalNode { left, right, .. }) => {
+            fold_expr(left);
+            fold_expr(right);
+        }
+        ExprNode::Identity(identity) | ExprNode::Reference(ReferenceNode { identity, .. }) => {
+            fold_identity(identity)
+        }
+        ExprNode::Group(GroupNode { value, .. })
+        | ExprNode::BinarySubscribe(BinarySubscribeNode { value, .. })
+        | ExprNode::Spread(SpreadNode { value, .. })
+        | ExprNode::Destructure(DestructureNode { value, .. })
+        | ExprNode::Return(ReturnNode { value, .. }) => fold_expr(value),
+        ExprNode::Deref(deref) => fold_expr(&mut deref.reference),
+        ExprNode::Slice(slice) => [&mut slice.start, &mut slice.end, &mut slice.step]
+            .into_iter()
+            .flatten()
+            .for_each(|bound| fold_expr(bound)),
+        ExprNode::Call(call) => {
+            fold_identity(&mut call.identity);
+            fold_constants(&mut call.arguments);
+        }
+        ExprNode::Assign(assign) => {
+            fold_identity(&mut assign.identity);
+            fold_expr(&mut assign.value);
+        }
+        ExprNode::Map(map) => {
+            for entry in map.properties.iter_mut() {
+                match entry {
+                    MapEntry::Property(property) => {
+                        fold_expr(&mut property.key);
+                        fold_expr(&mut property.value);
+                    }
+                    MapEntry::Spread(value) => fold_expr(value),
+                }
+            }
+        }
+        ExprNode::List(ListNode { elements, .. }) | ExprNode::Tuple(TupleNode { elements, .. }) => {
+            fold_constants(elements)
+        }
+        ExprNode::Function(function) => fold_constants(&mut function.body),
+        ExprNode::Class(class) => fold_constants(&mut class.body),
+        ExprNode::While(while_node) => {
+            fold_expr(&mut while_node.condition);
+            fold_constants(&mut while_node.body);
+        }
+        ExprNode::If(if_node) => {
+            fold_expr(&mut if_node.condition);
+            fold_constants(&mut if_node.then_body);
+            fold_constants(&mut if_node.else_body);
+        }
+        ExprNode::Match(match_node) => {
+            fold_expr(&mut match_node.subject);
+            for arm in match_node.arms.iter_mut() {
+                arm.guard.iter_mut().for_each(fold_expr);
+                fold_constants(&mut arm.body);
+            }
+        }
+        // literals, names and imports have nothing to fold
+        _ => {}
+    }
+    if let Some(folded) = fold_operation(expr) {
+        *expr = folded;
+    }
+}
+
+fn fold_identity(identity: &mut IdentityNode) {
+    // the base name and attribute names are plain strings, never folded themselves
+    identity.address.iter_mut().for_each(fold_expr);
+}
+
+/// The literal an operation on literals results in, keeping the operation's node id.
+fn fold_operation(expr: &ExprNode) -> Option<ExprNode> {
+    let (id, result) = match expr {
+        ExprNode::Binary(node) => (node.id, fold_binary(node)?),
+        ExprNode::Comparison(node) => (node.id, fold_comparison(node)?),
+        ExprNode::Logical(node) => (node.id, fold_logical(node)?),
+        _ => return None,
+    };
+    match result {
+        Value::Int(value) => Some(ExprNode::Int(IntNode { id, value: value.0 })),
+        Value::Float(value) => Some(ExprNode::Float(FloatNode { id, value: value.0 })),
+        Value::Bool(value) => Some(ExprNode::Bool(BoolNode { id, value: value.0 })),
+        Value::String(value) => Some(ExprNode::String(StringNode { id, value: value.0 })),
+        _ => None,
+    }
+}
+
+fn fold_binary(node: &BinaryNode) -> Option<Value> {
+    let (mut a, b) = operands(&node.left, &node.right)?;
+    if let (Value::Int(a), Value::Int(b)) = (&a, &b)
+        && int_operation_panics(node.operator, a.0, b.0)
+    {
+        return None;
+    }
+    let result = match node.operator {
+        TokenKind::Plus | TokenKind::Increment => a.add(&b),
+        TokenKind::Minus | TokenKind::Decrement => a.sub(&b),
+        TokenKind::Asterisk => a.mul(&b),
+        TokenKind::Slash => a.div(&b),
+        TokenKind::DoubleSlash => a.int_div(&b),
+        TokenKind::Exponent => a.pow(&b),
+        TokenKind::Modulo => a.modulus(&b),
+        _ => return None,
+    };
+    result.ok()
+}
+
+fn fold_comparison(node: &ComparisonNode) -> Option<Value> {
+    let (mut a, b) = operands(&node.left, &node.right)?;
+    // `!=` and membership tests are not `Binary` operations
+    let result = match node.operator {
+        TokenKind::Equals => a.equals(&b),
+        TokenKind::GreaterThan => a.greater(&b),
+        TokenKind::GreaterThanEquals => a.greater_equals(&b),
+        TokenKind::LessThan => a.less(&b),
+        TokenKind::LessThanEquals => a.less_equals(&b),
+        _ => return None,
+    };
+    result.ok()
+}
+
+fn fold_logical(node: &LogicalNode) -> Option<Value> {
+    // both sides are evaluated at runtime, so only literals on both sides fold
+    let (a, b) = (logical_operand(&node.left)?, logical_operand(&node.right)?);
+    match node.operator {
+        TokenKind::LogicalAND => Some(Value::bool(a.is_truthy() && b.is_truthy())),
+        TokenKind::LogicalOR => Some(Value::bool(a.is_truthy() || b.is_truthy())),
+        _ => None,
+    }
+}
+
+fn operands(left: &ExprNode, right: &ExprNode) -> Option<(Value, Value)> {
+    Some((literal(left)?, literal(right)?))
+}
+
+fn logical_operand(expr: &ExprNode) -> Option<Value> {
+    match expr {
+        ExprNode::Null => Some(Value::Null),
+        _ => literal(expr),
+    }
+}
+
+fn literal(expr: &ExprNode) -> Option<Value> {
+    match expr {
+        ExprNode::Int(_) | ExprNode::Float(_) | ExprNode::Bool(_) | ExprNode::String(_) => {
+            Value::from_expr(expr.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Integer operations the runtime performs with plain `i64` arithmetic, which panics on
+/// overflow and division by zero instead of raising.
+fn int_operation_panics(operator: TokenKind, a: i64, b: i64) -> bool {
+    match operator {
+        TokenKind::Plus | TokenKind::Increment => a.checked_add(b).is_none(),
+        TokenKind::Minus | TokenKind::Decrement => a.checked_sub(b).is_none(),
+        TokenKind::Asterisk => a.checked_mul(b).is_none(),
+        TokenKind::DoubleSlash => a.checked_div(b).is_none(),
+        TokenKind::Modulo => a.checked_rem(b).is_none(),
+        TokenKind::Exponent => b >= 0 && a.checked_pow(b as u32).is_none(),
+        _ => false,
+    }
+}
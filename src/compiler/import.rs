@@ -0,0 +1,51 @@
+use crate::compiler::Compiler;
+use crate::compiler::byte_operations::{ByteOp, OpIndex};
+use crate::compiler::code_object::CodeObject;
+use crate::compiler::compiler::CompileContext;
+use crate::compiler::vm_static::{cache_constant, cache_variable};
+use crate::parser::ExprNode;
+use crate::parser::nodes::ImportNode;
+use crate::runtime::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) fn import_module(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    import_node: ImportNode,
+) {
+    let path_index = cache_constant(
+        &mut code_object.borrow_mut(),
+        import_node.id,
+        Value::string(import_node.path),
+    );
+    if import_node.names.is_empty() {
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::with_op(ByteOp::Import, path_index),
+        );
+        let var_index = cache_variable(&mut code_object.borrow_mut(), &import_node.binding);
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::with_op(ByteOp::PreAssign, var_index),
+        );
+        return;
+    }
+    for name in import_node.names {
+        // modules are cached after the first import, so re-importing per name is cheap
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::with_op(ByteOp::Import, path_index),
+        );
+        compiler.compile_expr(ExprNode::string(name.clone()), &CompileContext::Identity);
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::without_op(ByteOp::AccessAttribute),
+        );
+        let var_index = cache_variable(&mut code_object.borrow_mut(), &name);
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::with_op(ByteOp::PreAssign, var_index),
+        );
+    }
+}
@@ -5,7 +5,10 @@ use crate::compiler::op::call;
 use crate::compiler::vm_static::{cache_constant, cache_variable};
 use crate::compiler::{ByteOp, Compiler};
 use crate::parser::ExprNode;
-use crate::parser::nodes::{AccessAttributeNode, BinarySubscribeNode, IdentityNode, StringNode};
+use crate::parser::nodes::{
+    AccessAttributeNode, BinarySubscribeNode, DerefNode, IdentityNode, ReferenceNode, StringNode,
+};
+use crate::runtime::builtins::builtin_index;
 use crate::runtime::value::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -15,7 +18,7 @@ fn binary_subscribe(
     code_object: Rc<RefCell<CodeObject>>,
     node: BinarySubscribeNode,
 ) {
-    compiler.compile_expr(*node.value, &CompileContext::Normal);
+    compiler.compile_expr(*node.value, &CompileContext::Binary);
     compiler.push_op(
         &mut *code_object.borrow_mut(),
         OpIndex::without_op(ByteOp::BinarySubscribe),
@@ -48,12 +51,18 @@ pub(crate) fn identity(
             }
             _ => load_local_or_nonlocal(compiler, code_object.clone(), string_base),
         },
-        Some(ExprNode::Call(func_call_base)) => call(
-            compiler,
-            code_object.clone(),
-            func_call_base,
-            &CompileContext::Identity,
-        ),
+        Some(ExprNode::Group(group_base)) => {
+            compiler.compile_expr(*group_base.value, &CompileContext::Identity)
+        }
+        Some(ExprNode::Call(func_call_base)) => {
+            // a trailing call's result is only kept if the enclosing expression wants it
+            let call_context = if identity_address_iter.len() == 0 {
+                context
+            } else {
+                &CompileContext::Identity
+            };
+            call(compiler, code_object.clone(), func_call_base, call_context)
+        }
         _ => panic!(
             "Unexpected identity base: {:?}",
             identity_address_iter.collect::<Vec<_>>()
@@ -127,24 +136,82 @@ pub(crate) fn load_or_cache_local(
     });
 }
 
+/// `&name`: the variable's slot in its own scope, the same lookup as loading its value.
+pub(crate) fn reference(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    reference_node: ReferenceNode,
+) {
+    let address = reference_node.identity.address;
+    let [ExprNode::String(name)] = address.as_slice() else {
+        panic!("Only variables can be referenced, got {:?}", address)
+    };
+    let local_index = code_object
+        .borrow()
+        .variable_index_lookup
+        .get(&name.value)
+        .copied();
+    if let Some(var_index) = local_index {
+        compiler.push_op(
+            &mut code_object.borrow_mut(),
+            OpIndex::with_op(ByteOp::LoadReference, var_index),
+        );
+        return;
+    }
+    let (scope_id, var_index) = compiler
+        .scope_stack
+        .iter()
+        .rev()
+        .find_map(|scope| {
+            let scope = scope.borrow();
+            scope
+                .variable_index_lookup
+                .get(&name.value)
+                .map(|var_index| (scope.id, *var_index))
+        })
+        .unwrap_or_else(|| panic!("Variable {:?} not found in scope stack", name.value));
+    let mut_code_obj = &mut *code_object.borrow_mut();
+    compiler.push_op(mut_code_obj, OpIndex::with_op(ByteOp::LoadScope, scope_id));
+    compiler.push_op(
+        mut_code_obj,
+        OpIndex::with_op(ByteOp::LoadNonlocalReference, var_index),
+    );
+}
+
+pub(crate) fn dereference(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    deref_node: DerefNode,
+) {
+    compiler.compile_expr(*deref_node.reference, &CompileContext::Binary);
+    compiler.push_op(
+        &mut code_object.borrow_mut(),
+        OpIndex::without_op(ByteOp::Deref),
+    );
+}
+
 pub(crate) fn load_local_or_nonlocal(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     node: StringNode,
 ) {
     if !load_cached_local(compiler, code_object.clone(), &node).is_ok() {
-        let (scope, var_index) = compiler
-            .scope_stack
-            .iter()
-            .rev()
-            .find_map(|scope| {
-                scope
-                    .borrow()
-                    .variable_index_lookup
-                    .get(&node.value)
-                    .map(|var_index| (scope.clone(), *var_index))
-            })
-            .unwrap_or_else(|| panic!("Variable {:?} not found in scope stack", node.value));
+        let scope_variable = compiler.scope_stack.iter().rev().find_map(|scope| {
+            scope
+                .borrow()
+                .variable_index_lookup
+                .get(&node.value)
+                .map(|var_index| (scope.clone(), *var_index))
+        });
+        let Some((scope, var_index)) = scope_variable else {
+            let builtin_index = builtin_index(&node.value)
+                .unwrap_or_else(|| panic!("Variable {:?} not found in scope stack", node.value));
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::LoadBuiltin, builtin_index),
+            );
+            return;
+        };
         let scope_id = scope.borrow().id;
         let mut_code_obj = &mut *code_object.borrow_mut();
         compiler.push_op(mut_code_obj, OpIndex::with_op(ByteOp::LoadScope, scope_id));
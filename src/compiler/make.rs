@@ -5,7 +5,9 @@ use crate::compiler::load::{identity, load_constant};
 use crate::compiler::vm_static::*;
 use crate::compiler::{ByteOp, Compiler};
 use crate::parser::ExprNode;
-use crate::parser::nodes::{ClassNode, FunctionNode, ListNode, MapNode};
+use crate::parser::nodes::{
+    ClassNode, FunctionNode, ListNode, MapEntry, MapNode, SliceNode, TupleNode,
+};
 use crate::runtime::value::{FunctionValue, Value};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -28,15 +30,37 @@ fn make_runtime_map(
     code_object: Rc<RefCell<CodeObject>>,
     object_node: MapNode,
 ) {
-    let obj_size = object_node.properties.len() * 2;
-    object_node.properties.into_iter().for_each(|property| {
-        compiler.compile_expr(property.key, &CompileContext::Normal);
-        compiler.compile_expr(property.value, &CompileContext::Normal);
+    // properties before the first spread are built at once, the rest are merged in order
+    let leading = object_node
+        .properties
+        .iter()
+        .take_while(|entry| matches!(entry, MapEntry::Property(_)))
+        .count();
+    let mut entries = object_node.properties.into_iter();
+    entries.by_ref().take(leading).for_each(|entry| {
+        if let MapEntry::Property(property) = entry {
+            compiler.compile_expr(property.key, &CompileContext::Binary);
+            compiler.compile_expr(property.value, &CompileContext::Binary);
+        }
     });
     compiler.push_op(
         &mut *code_object.borrow_mut(),
-        OpIndex::with_op(ByteOp::MakeMap, obj_size),
+        OpIndex::with_op(ByteOp::MakeMap, leading * 2),
     );
+    for entry in entries {
+        let op = match entry {
+            MapEntry::Property(property) => {
+                compiler.compile_expr(property.key, &CompileContext::Binary);
+                compiler.compile_expr(property.value, &CompileContext::Binary);
+                ByteOp::MapInsert
+            }
+            MapEntry::Spread(value) => {
+                compiler.compile_expr(value, &CompileContext::Binary);
+                ByteOp::MapUpdate
+            }
+        };
+        compiler.push_op(&mut *code_object.borrow_mut(), OpIndex::without_op(op));
+    }
 }
 
 pub(crate) fn make_list(
@@ -57,18 +81,83 @@ fn make_runtime_list(
     code_object: Rc<RefCell<CodeObject>>,
     list_node: ListNode,
 ) {
-    let list_len = list_node.elements.len();
-    list_node.elements.into_iter().for_each(|element| {
-        compiler.compile_expr(element, &CompileContext::Normal);
+    build_list(compiler, &code_object, list_node.elements);
+}
+
+/// Emits the ops leaving a list of `elements` on the stack, expanding spread elements in place.
+pub(crate) fn build_list(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    elements: Vec<ExprNode>,
+) {
+    // elements before the first spread are built at once, the rest are appended in order
+    let leading = elements
+        .iter()
+        .take_while(|element| !matches!(element, ExprNode::Spread(_)))
+        .count();
+    let mut elements = elements.into_iter();
+    elements.by_ref().take(leading).for_each(|element| {
+        compiler.compile_expr(element, &CompileContext::Binary);
     });
     compiler.push_op(
-        &mut *code_object.borrow_mut(),
-        OpIndex::with_op(ByteOp::MakeList, list_len),
+        &mut code_object.borrow_mut(),
+        OpIndex::with_op(ByteOp::MakeList, leading),
+    );
+    for element in elements {
+        let op = match element {
+            ExprNode::Spread(spread) => {
+                compiler.compile_expr(*spread.value, &CompileContext::Binary);
+                ByteOp::ListExtend
+            }
+            element => {
+                compiler.compile_expr(element, &CompileContext::Binary);
+                ByteOp::ListAppend
+            }
+        };
+        compiler.push_op(&mut code_object.borrow_mut(), OpIndex::without_op(op));
+    }
+}
+
+pub(crate) fn make_tuple(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    tuple_node: TupleNode,
+) {
+    let tuple_len = tuple_node.elements.len();
+    tuple_node.elements.into_iter().for_each(|element| {
+        compiler.compile_expr(element, &CompileContext::Binary);
+    });
+    compiler.push_op(
+        &mut code_object.borrow_mut(),
+        OpIndex::with_op(ByteOp::MakeTuple, tuple_len),
+    );
+}
+
+pub(crate) fn make_slice(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    slice_node: SliceNode,
+) {
+    for part in [slice_node.start, slice_node.end, slice_node.step] {
+        match part {
+            Some(part) => compiler.compile_expr(*part, &CompileContext::Binary),
+            None => compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::without_op(ByteOp::LoadNull),
+            ),
+        }
+    }
+    compiler.push_op(
+        &mut code_object.borrow_mut(),
+        OpIndex::without_op(ByteOp::MakeSlice),
     );
 }
 
 fn get_function(compiler: &mut Compiler, function_node: FunctionNode) -> FunctionValue {
-    let func_code_obj = Rc::new(RefCell::new(CodeObject::from_function(&function_node))); // pre caches params
+    let func_code_obj = Rc::new(RefCell::new(CodeObject::from_function(
+        &function_node,
+        compiler.file.clone(),
+    ))); // pre caches params
     compiler.compile_into(
         function_node.body,
         &CompileContext::Function,
@@ -76,7 +165,7 @@ fn get_function(compiler: &mut Compiler, function_node: FunctionNode) -> Functio
     );
     FunctionValue::new(
         function_node.arguments,
-        Rc::try_unwrap(func_code_obj).unwrap().into_inner(),
+        Rc::new(Rc::try_unwrap(func_code_obj).unwrap().into_inner()),
     )
 }
 
@@ -98,10 +187,13 @@ pub(crate) fn make_function(
 pub(crate) fn make_method(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
-    function_node: FunctionNode,
+    mut function_node: FunctionNode,
 ) {
-    let mut_code_obj = &mut *code_object.borrow_mut();
+    // methods are named after their class, the code object of the body being compiled
+    function_node.name = format!("{}.{}", code_object.borrow().name, function_node.name);
+    // compiled before borrowing the class body, which the method may load names from
     let func_value = get_function(compiler, function_node);
+    let mut_code_obj = &mut *code_object.borrow_mut();
     let method_id = func_value.id;
     let method_value = Value::method(func_value, None); // caller known only at runtime
     let method_const_index = cache_constant(mut_code_obj, method_id, method_value);
@@ -130,7 +222,7 @@ pub(crate) fn make_class(
     };
     let class_value = Value::class(
         None,
-        compiler.compile(class_node.body, &CompileContext::Class),
+        Rc::new(compiler.compile(class_node.name, class_node.body, &CompileContext::Class)),
     ); // inherits at runtime
     let class_const_index = cache_constant(&mut *code_object.borrow_mut(), class_id, class_value);
     let mut_code_obj = &mut *code_object.borrow_mut();
@@ -1,11 +1,18 @@
 mod access;
 pub mod byte_operations;
+pub mod bytecode_file;
 mod closure;
 pub mod code_object;
 pub mod compiler;
+pub mod disassembler;
+mod fold;
+mod import;
 mod load;
 mod make;
 mod op;
+mod pattern;
+mod peephole;
+pub mod verifier;
 mod vm_static;
 
 pub use byte_operations::ByteOp;
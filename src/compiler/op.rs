@@ -2,12 +2,14 @@ use crate::compiler::byte_operations::{ByteComparisonOp, OpIndex};
 use crate::compiler::code_object::CodeObject;
 use crate::compiler::compiler::CompileContext;
 use crate::compiler::load::{identity, identity_popped_head};
-use crate::compiler::vm_static::cache_variable;
+use crate::compiler::make::build_list;
+use crate::compiler::vm_static::{HiddenSlots, cache_variable};
 use crate::compiler::{ByteOp, Compiler};
 use crate::lexer::TokenKind;
 use crate::parser::ExprNode;
 use crate::parser::nodes::{
-    AssignNode, BinaryNode, CallNode, ComparisonNode, LogicalNode, ReturnNode,
+    AssignNode, AssignTarget, BinaryNode, CallNode, ComparisonNode, DestructureNode, LogicalNode,
+    ReturnNode,
 };
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -66,18 +68,24 @@ pub(crate) fn assign(
         };
         assign_node.identity.address.pop().unwrap()
     };
+    // function and class definitions depend on the enclosing context (methods in class bodies),
+    // any other value is compiled as an operand that stays on the stack for the store
+    let value_ctx = match *assign_node.value {
+        ExprNode::Function(_) | ExprNode::Class(_) => ctx,
+        _ => &CompileContext::Binary,
+    };
     match head {
         ExprNode::BinarySubscribe(binary_subscribe_node) => {
-            compiler.compile_expr(*binary_subscribe_node.value, ctx);
-            compiler.compile_expr(*assign_node.value, ctx);
+            compiler.compile_expr(*binary_subscribe_node.value, &CompileContext::Binary);
+            compiler.compile_expr(*assign_node.value, value_ctx);
             compiler.push_op(
                 &mut *code_object.borrow_mut(),
                 OpIndex::without_op(ByteOp::AssignSubscribe),
             );
         }
         ExprNode::AccessAttribute(access_attribute_node) => {
-            compiler.compile_expr(*access_attribute_node.value, ctx);
-            compiler.compile_expr(*assign_node.value, ctx);
+            compiler.compile_expr(*access_attribute_node.value, &CompileContext::Binary);
+            compiler.compile_expr(*assign_node.value, value_ctx);
             compiler.push_op(
                 &mut *code_object.borrow_mut(),
                 OpIndex::without_op(ByteOp::AssignAttribute),
@@ -85,7 +93,7 @@ pub(crate) fn assign(
         }
         ExprNode::String(string_node) => {
             let var_index = cache_variable(&mut *code_object.borrow_mut(), &string_node.value);
-            compiler.compile_expr(*assign_node.value, ctx);
+            compiler.compile_expr(*assign_node.value, value_ctx);
             compiler.push_op(
                 &mut *code_object.borrow_mut(),
                 OpIndex::with_op(ByteOp::PreAssign, var_index),
@@ -98,44 +106,148 @@ pub(crate) fn assign(
     }
 }
 
+pub(crate) fn destructure(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    destructure_node: DestructureNode,
+) {
+    // the whole value is evaluated before any target is assigned, so `a, b = b, a` swaps
+    compiler.compile_expr(*destructure_node.value, &CompileContext::Binary);
+    let mut slots = HiddenSlots::new("unpack", destructure_node.id);
+    store_target(compiler, &code_object, destructure_node.target, &mut slots);
+}
+
+/// Emits the stores of the value on top of the stack into `target`, consuming it.
+fn store_target(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    target: AssignTarget,
+    slots: &mut HiddenSlots,
+) {
+    match target {
+        AssignTarget::Identity(identity) if identity.address.len() == 1 => {
+            let Some(ExprNode::String(name)) = identity.address.into_iter().next() else {
+                panic!("Invalid assignment target")
+            };
+            let var_index = cache_variable(&mut code_object.borrow_mut(), &name.value);
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::PreAssign, var_index),
+            );
+        }
+        AssignTarget::Identity(identity) => {
+            // attribute and subscript stores expect the value above their container
+            let value_slot = slots.next(code_object);
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::PreAssign, value_slot),
+            );
+            let head = identity_popped_head(
+                compiler,
+                code_object.clone(),
+                identity,
+                &CompileContext::Assignment,
+            );
+            let (key, store_op) = match head {
+                ExprNode::BinarySubscribe(node) => (*node.value, ByteOp::AssignSubscribe),
+                ExprNode::AccessAttribute(node) => (*node.value, ByteOp::AssignAttribute),
+                _ => panic!("Invalid assignment target {:?}", head),
+            };
+            compiler.compile_expr(key, &CompileContext::Binary);
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::LoadLocal, value_slot),
+            );
+            compiler.push_op(&mut code_object.borrow_mut(), OpIndex::without_op(store_op));
+        }
+        AssignTarget::Sequence(targets) => {
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::UnpackSequence, targets.len()),
+            );
+            for target in targets {
+                store_target(compiler, code_object, target, slots);
+            }
+        }
+        AssignTarget::Deref(reference) => {
+            compiler.compile_expr(*reference, &CompileContext::Binary);
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::without_op(ByteOp::StoreDeref),
+            );
+        }
+        AssignTarget::Map(properties) => {
+            let map_slot = slots.next(code_object);
+            compiler.push_op(
+                &mut code_object.borrow_mut(),
+                OpIndex::with_op(ByteOp::PreAssign, map_slot),
+            );
+            for (key, target) in properties {
+                compiler.push_op(
+                    &mut code_object.borrow_mut(),
+                    OpIndex::with_op(ByteOp::LoadLocal, map_slot),
+                );
+                compiler.compile_expr(key, &CompileContext::Binary);
+                compiler.push_op(
+                    &mut code_object.borrow_mut(),
+                    OpIndex::without_op(ByteOp::BinarySubscribe),
+                );
+                store_target(compiler, code_object, target, slots);
+            }
+        }
+    }
+}
+
 pub(crate) fn return_value(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     return_node: ReturnNode,
     context: &CompileContext,
 ) {
-    compiler.compile_expr(*return_node.value, &CompileContext::Return);
+    match returned_call(*return_node.value) {
+        // `return f(...)` lets the callee's frame take over this one
+        Ok(call_node) => push_call(
+            compiler,
+            &code_object,
+            call_node,
+            &CompileContext::Return,
+            true,
+        ),
+        Err(value) => compiler.compile_expr(value, &CompileContext::Return),
+    }
+    // returns the result of calls that could not take over the frame
     compiler.push_op(
         &mut *code_object.borrow_mut(),
         OpIndex::without_op(ByteOp::ReturnValue),
     );
 }
 
+/// The call a `return` hands on the result of, or the returned expression if it is not one.
+fn returned_call(value: ExprNode) -> Result<CallNode, ExprNode> {
+    match value {
+        ExprNode::Call(call_node) => Ok(call_node),
+        // calls are parsed as the base of an identity, without parts after it here
+        ExprNode::Identity(mut identity)
+            if identity.address.len() == 1 && matches!(identity.address[0], ExprNode::Call(_)) =>
+        {
+            match identity.address.pop() {
+                Some(ExprNode::Call(call_node)) => Ok(call_node),
+                _ => unreachable!(),
+            }
+        }
+        value => Err(value),
+    }
+}
+
 pub(crate) fn call(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     call_node: CallNode,
     context: &CompileContext,
 ) {
-    let arg_count = call_node.arguments.len();
-    call_node
-        .arguments
-        .into_iter()
-        .for_each(|arg| compiler.compile_expr(arg, &CompileContext::Argument));
-    identity(compiler, code_object.clone(), call_node.identity, context);
-    compiler.push_op(
-        &mut *code_object.borrow_mut(),
-        OpIndex::with_op(ByteOp::Call, arg_count),
-    );
+    push_call(compiler, &code_object, call_node, context, false);
     // if the return value is uncaught, discard it after the call
-    if !matches!(
-        context,
-        CompileContext::Assignment
-            | CompileContext::Argument
-            | CompileContext::Identity
-            | CompileContext::Binary
-            | CompileContext::Return
-    ) {
+    if !context.wants_value() {
         compiler.push_op(
             &mut *code_object.borrow_mut(),
             OpIndex::without_op(ByteOp::Pop),
@@ -143,11 +255,44 @@ pub(crate) fn call(
     }
 }
 
+/// Pushes the arguments, the callee and the call; `tail` calls take over the returning frame,
+/// which spread calls never do.
+fn push_call(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    call_node: CallNode,
+    context: &CompileContext,
+    tail: bool,
+) {
+    let arg_count = call_node.arguments.len();
+    let spread = call_node
+        .arguments
+        .iter()
+        .any(|arg| matches!(arg, ExprNode::Spread(_)));
+    let call_op = if spread {
+        // spread arguments are collected into a single list, expanded by the call
+        build_list(compiler, code_object, call_node.arguments);
+        OpIndex::without_op(ByteOp::CallSpread)
+    } else {
+        call_node
+            .arguments
+            .into_iter()
+            .for_each(|arg| compiler.compile_expr(arg, &CompileContext::Argument));
+        let operation = if tail { ByteOp::TailCall } else { ByteOp::Call };
+        OpIndex::with_op(operation, arg_count)
+    };
+    identity(compiler, code_object.clone(), call_node.identity, context);
+    compiler.push_op(&mut *code_object.borrow_mut(), call_op);
+}
+
 pub(crate) fn comparison(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
     comparison_node: ComparisonNode,
 ) {
+    if matches!(comparison_node.operator, TokenKind::In | TokenKind::NotIn) {
+        return contains(compiler, code_object, comparison_node);
+    }
     let operand = match comparison_node.operator {
         TokenKind::Equals => ByteComparisonOp::Equal,
         TokenKind::NotEquals => ByteComparisonOp::NotEqual,
@@ -168,6 +313,21 @@ pub(crate) fn comparison(
     );
 }
 
+fn contains(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    comparison_node: ComparisonNode,
+) {
+    // operand 1 negates the membership test (`not in`)
+    let is_negated = comparison_node.operator == TokenKind::NotIn;
+    compiler.compile_expr(*comparison_node.left, &CompileContext::Binary);
+    compiler.compile_expr(*comparison_node.right, &CompileContext::Binary);
+    compiler.push_op(
+        &mut code_object.borrow_mut(),
+        OpIndex::with_op(ByteOp::Contains, is_negated as usize),
+    );
+}
+
 pub(crate) fn logical(
     compiler: &mut Compiler,
     code_object: Rc<RefCell<CodeObject>>,
@@ -0,0 +1,279 @@
+use crate::compiler::byte_operations::{ByteComparisonOp, OpIndex};
+use crate::compiler::closure::{
+    branches_leave_value, make_closure_body, make_value_closure_body, patch_jump_to_here,
+    push_jump_placeholder,
+};
+use crate::compiler::code_object::CodeObject;
+use crate::compiler::compiler::CompileContext;
+use crate::compiler::load::identity;
+use crate::compiler::vm_static::{HiddenSlots, cache_variable};
+use crate::compiler::{ByteOp, Compiler};
+use crate::parser::ExprNode;
+use crate::parser::nodes::{MatchNode, PatternNode};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+fn push(compiler: &mut Compiler, code_object: &Rc<RefCell<CodeObject>>, op: OpIndex) {
+    compiler.push_op(&mut code_object.borrow_mut(), op);
+}
+
+fn store_slot(compiler: &mut Compiler, code_object: &Rc<RefCell<CodeObject>>, slot: usize) {
+    push(
+        compiler,
+        code_object,
+        OpIndex::with_op(ByteOp::PreAssign, slot),
+    );
+}
+
+fn load_slot(compiler: &mut Compiler, code_object: &Rc<RefCell<CodeObject>>, slot: usize) {
+    push(
+        compiler,
+        code_object,
+        OpIndex::with_op(ByteOp::LoadLocal, slot),
+    );
+}
+
+fn push_fail_jump(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    fail_jumps: &mut Vec<usize>,
+) {
+    fail_jumps.push(push_jump_placeholder(
+        compiler,
+        code_object,
+        ByteOp::PopJumpIfFalse,
+    ));
+}
+
+/// Emits the checks of `pattern` against the value stored in `subject_slot`.
+/// The emitted code is stack neutral, every failed check jumps to one of `fail_jumps`.
+fn compile_pattern(
+    compiler: &mut Compiler,
+    code_object: &Rc<RefCell<CodeObject>>,
+    pattern: PatternNode,
+    subject_slot: usize,
+    slots: &mut HiddenSlots,
+    fail_jumps: &mut Vec<usize>,
+) {
+    match pattern {
+        PatternNode::Wildcard => {}
+        PatternNode::Binding(name) => {
+            let var_index = cache_variable(&mut code_object.borrow_mut(), &name);
+            load_slot(compiler, code_object, subject_slot);
+            store_slot(compiler, code_object, var_index);
+        }
+        PatternNode::Value(value) => {
+            load_slot(compiler, code_object, subject_slot);
+            compiler.compile_expr(value, &CompileContext::Binary);
+            push(
+                compiler,
+                code_object,
+                OpIndex::with_op(ByteOp::Compare, ByteComparisonOp::Equal as usize),
+            );
+            push_fail_jump(compiler, code_object, fail_jumps);
+        }
+        PatternNode::Or(alternatives) => {
+            let mut success_jumps = Vec::new();
+            let last_index = alternatives.len() - 1;
+            for (i, alternative) in alternatives.into_iter().enumerate() {
+                if i == last_index {
+                    compile_pattern(
+                        compiler,
+                        code_object,
+                        alternative,
+                        subject_slot,
+                        slots,
+                        fail_jumps,
+                    );
+                    break;
+                }
+                // a failed alternative falls through to the next one
+                let mut alternative_fail_jumps = Vec::new();
+                compile_pattern(
+                    compiler,
+                    code_object,
+                    alternative,
+                    subject_slot,
+                    slots,
+                    &mut alternative_fail_jumps,
+                );
+                success_jumps.push(push_jump_placeholder(compiler, code_object, ByteOp::Jump));
+                for jump in alternative_fail_jumps {
+                    patch_jump_to_here(code_object, jump);
+                }
+            }
+            for jump in success_jumps {
+                patch_jump_to_here(code_object, jump);
+            }
+        }
+        PatternNode::List(elements) => {
+            load_slot(compiler, code_object, subject_slot);
+            push(
+                compiler,
+                code_object,
+                OpIndex::with_op(ByteOp::MatchSequence, elements.len()),
+            );
+            push_fail_jump(compiler, code_object, fail_jumps);
+            for (i, element) in elements.into_iter().enumerate() {
+                if matches!(element, PatternNode::Wildcard) {
+                    continue;
+                }
+                let element_slot = slots.next(code_object);
+                load_slot(compiler, code_object, subject_slot);
+                compiler.compile_expr(ExprNode::int(i as i64), &CompileContext::Binary);
+                push(
+                    compiler,
+                    code_object,
+                    OpIndex::without_op(ByteOp::BinarySubscribe),
+                );
+                store_slot(compiler, code_object, element_slot);
+                compile_pattern(
+                    compiler,
+                    code_object,
+                    element,
+                    element_slot,
+                    slots,
+                    fail_jumps,
+                );
+            }
+        }
+        PatternNode::Map(properties) => {
+            load_slot(compiler, code_object, subject_slot);
+            push(
+                compiler,
+                code_object,
+                OpIndex::without_op(ByteOp::MatchMapping),
+            );
+            push_fail_jump(compiler, code_object, fail_jumps);
+            for property in properties {
+                compiler.compile_expr(property.key.clone(), &CompileContext::Binary);
+                load_slot(compiler, code_object, subject_slot);
+                push(compiler, code_object, OpIndex::with_op(ByteOp::Contains, 0));
+                push_fail_jump(compiler, code_object, fail_jumps);
+                if matches!(property.pattern, PatternNode::Wildcard) {
+                    continue;
+                }
+                let value_slot = slots.next(code_object);
+                load_slot(compiler, code_object, subject_slot);
+                compiler.compile_expr(property.key, &CompileContext::Binary);
+                push(
+                    compiler,
+                    code_object,
+                    OpIndex::without_op(ByteOp::BinarySubscribe),
+                );
+                store_slot(compiler, code_object, value_slot);
+                compile_pattern(
+                    compiler,
+                    code_object,
+                    property.pattern,
+                    value_slot,
+                    slots,
+                    fail_jumps,
+                );
+            }
+        }
+        PatternNode::Class(class_identity, arguments) => {
+            let class_slot = slots.next(code_object);
+            identity(
+                compiler,
+                code_object.clone(),
+                class_identity,
+                &CompileContext::Binary,
+            );
+            store_slot(compiler, code_object, class_slot);
+            load_slot(compiler, code_object, subject_slot);
+            load_slot(compiler, code_object, class_slot);
+            push(
+                compiler,
+                code_object,
+                OpIndex::without_op(ByteOp::MatchClass),
+            );
+            push_fail_jump(compiler, code_object, fail_jumps);
+            // positional sub-patterns match the attributes named by the class init parameters
+            for (i, argument) in arguments.into_iter().enumerate() {
+                if matches!(argument, PatternNode::Wildcard) {
+                    continue;
+                }
+                let attribute_slot = slots.next(code_object);
+                load_slot(compiler, code_object, subject_slot);
+                load_slot(compiler, code_object, class_slot);
+                push(
+                    compiler,
+                    code_object,
+                    OpIndex::with_op(ByteOp::LoadMatchArg, i),
+                );
+                push(
+                    compiler,
+                    code_object,
+                    OpIndex::without_op(ByteOp::AccessAttribute),
+                );
+                store_slot(compiler, code_object, attribute_slot);
+                compile_pattern(
+                    compiler,
+                    code_object,
+                    argument,
+                    attribute_slot,
+                    slots,
+                    fail_jumps,
+                );
+            }
+        }
+    }
+}
+
+pub(crate) fn match_closure(
+    compiler: &mut Compiler,
+    code_object: Rc<RefCell<CodeObject>>,
+    match_node: MatchNode,
+    context: &CompileContext,
+) {
+    let mut slots = HiddenSlots::new("match", match_node.id);
+    let subject_slot = slots.next(&code_object);
+    compiler.compile_expr(*match_node.subject, &CompileContext::Binary);
+    store_slot(compiler, &code_object, subject_slot);
+
+    // as a value every arm leaves exactly one value, null when no arm matches; so do statements
+    // with an arm ending in a value, which is their result
+    let wants_value =
+        context.wants_value() || branches_leave_value(match_node.arms.iter().map(|arm| &arm.body));
+    let context = if context.wants_value() {
+        context
+    } else {
+        &CompileContext::Result
+    };
+    let mut end_jumps = Vec::new();
+    for arm in match_node.arms {
+        let mut fail_jumps = Vec::new();
+        compile_pattern(
+            compiler,
+            &code_object,
+            arm.pattern,
+            subject_slot,
+            &mut slots,
+            &mut fail_jumps,
+        );
+        if let Some(guard) = arm.guard {
+            compiler.compile_expr(guard, &CompileContext::Binary);
+            push_fail_jump(compiler, &code_object, &mut fail_jumps);
+        }
+        if wants_value {
+            make_value_closure_body(compiler, code_object.clone(), arm.body, context);
+        } else {
+            make_closure_body(compiler, code_object.clone(), arm.body);
+        }
+        end_jumps.push(push_jump_placeholder(compiler, &code_object, ByteOp::Jump));
+        for jump in fail_jumps {
+            patch_jump_to_here(&code_object, jump);
+        }
+    }
+    if wants_value {
+        push(
+            compiler,
+            &code_object,
+            OpIndex::without_op(ByteOp::LoadNull),
+        );
+    }
+    for jump in end_jumps {
+        patch_jump_to_here(&code_object, jump);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::OpIndex;
+use crate::compiler::code_object::CodeObject;
+use crate::runtime::value::Value;
+use std::collections::HashSet;
+
+/// Rewrites the naive operation sequences the compiler emits: jump chains are threaded, values
+/// pushed only to be popped and conditional jumps on constants are dropped, and operations no
+/// path reaches (like those after a `ReturnValue`) are removed. Jump operands and the line
+/// table are relocated to the operations' new positions.
+pub(crate) fn optimize(code_object: &mut CodeObject) {
+    loop {
+        thread_jumps(&mut code_object.operations);
+        let removed = dead_operations(code_object);
+        if !removed.contains(&true) {
+            break;
+        }
+        compact(code_object, &removed);
+    }
+}
+
+/// Points jumps landing on an unconditional jump straight at its destination.
+fn thread_jumps(operations: &mut [OpIndex]) {
+    for i in 0..operations.len() {
+        if !operations[i].operation.is_jump() {
+            continue;
+        }
+        let mut target = operations[i].operand;
+        // a chain longer than the code is a loop of jumps, left as it is
+        for _ in 0..operations.len() {
+            match operations.get(target) {
+                Some(next) if matches!(next.operation, ByteOp::Jump) && next.operand != target => {
+                    target = next.operand
+                }
+                _ => break,
+            }
+        }
+        operations[i].operand = target;
+    }
+}
+
+/// Marks the operations that can go, rewriting conditional jumps on constants as it goes.
+fn dead_operations(code_object: &mut CodeObject) -> Vec<bool> {
+    let operations = &mut code_object.operations;
+    let mut removed = vec![false; operations.len()];
+    let targets: HashSet<usize> = operations
+        .iter()
+        .filter(|op| op.operation.is_jump())
+        .map(|op| op.operand)
+        .collect();
+    let mut i = 0;
+    while i < operations.len() {
+        let op = operations[i];
+        let next = operations.get(i + 1).copied();
+        // the second operation of a pair must not be entered by a jump of its own
+        let pair = next.filter(|_| !targets.contains(&(i + 1)));
+        match (op.operation, pair.map(|next| next.operation)) {
+            (ByteOp::LoadConstant | ByteOp::LoadNull | ByteOp::LoadLocal, Some(ByteOp::Pop)) => {
+                removed[i] = true;
+                removed[i + 1] = true;
+                i += 2;
+                continue;
+            }
+            (ByteOp::LoadConstant, Some(ByteOp::PopJumpIfFalse)) => {
+                if let Some(truthy) = constant_truthiness(&code_object.constants[op.operand]) {
+                    removed[i] = true;
+                    if truthy {
+                        removed[i + 1] = true;
+                    } else {
+                        operations[i + 1] =
+                            OpIndex::with_op(ByteOp::Jump, operations[i + 1].operand);
+                    }
+                    i += 2;
+                    continue;
+                }
+            }
+            (ByteOp::Jump, _) if op.operand == i + 1 => removed[i] = true,
+            _ => {}
+        }
+        i += 1;
+    }
+    for (i, reachable) in reachable_operations(operations).into_iter().enumerate() {
+        removed[i] |= !reachable;
+    }
+    removed
+}
+
+/// Truthiness of a constant the compiler loads as a condition, if it is a plain literal.
+fn constant_truthiness(constant: &std::rc::Rc<std::cell::RefCell<Value>>) -> Option<bool> {
+    match &*constant.borrow() {
+        value @ (Value::Int(_) | Value::Float(_) | Value::Bool(_) | Value::String(_)) => {
+            Some(value.is_truthy())
+        }
+        _ => None,
+    }
+}
+
+fn reachable_operations(operations: &[OpIndex]) -> Vec<bool> {
+    let mut reachable = vec![false; operations.len()];
+    let mut pending = vec![0];
+    while let Some(i) = pending.pop() {
+        // running past the end (or jumping there) returns
+        if i >= operations.len() || reachable[i] {
+            continue;
+        }
+        reachable[i] = true;
+        let op = operations[i];
+        match op.operation {
+            ByteOp::ReturnValue => {}
+            ByteOp::Jump => pending.push(op.operand),
+            ByteOp::PopJumpIfFalse => pending.extend([i + 1, op.operand]),
+            _ => pending.push(i + 1),
+        }
+    }
+    reachable
+}
+
+/// Drops the removed operations; whatever pointed at one now points at the next that remains.
+fn compact(code_object: &mut CodeObject, removed: &[bool]) {
+    let mut new_index = Vec::with_capacity(removed.len() + 1);
+    let mut kept = 0;
+    for is_removed in removed {
+        new_index.push(kept);
+        kept += !is_removed as usize;
+    }
+    new_index.push(kept);
+
+    let operations = std::mem::take(&mut code_object.operations);
+    code_object.operations = operations
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, is_removed)| !**is_removed)
+        .map(|(mut op, _)| {
+            if op.operation.is_jump() {
+                op.operand = new_index[op.operand];
+            }
+            op
+        })
+        .collect();
+
+    let line_table = std::mem::take(&mut code_object.line_table);
+    for (start, line) in line_table {
+        let start = new_index[start];
+        // a line whose operations all went is superseded by the one after it
+        if code_object
+            .line_table
+            .last()
+            .is_some_and(|(last_start, _)| *last_start == start)
+        {
+            code_object.line_table.pop();
+        }
+        code_object.mark_line(start, line);
+    }
+}
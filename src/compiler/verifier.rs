@@ -0,0 +1,402 @@
+use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::{ByteComparisonOp, OpIndex};
+use crate::compiler::code_object::CodeObject;
+use crate::runtime::builtins::BUILTINS;
+use crate::runtime::value::{FunctionValue, Value};
+use std::fmt;
+
+/// Where verification failed: the code object (by name), the offset of the operation in its
+/// code, and what is wrong there.
+#[derive(Debug, PartialEq)]
+pub struct VerifyError {
+    pub code_object: String,
+    pub offset: usize,
+    pub kind: VerifyErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VerifyErrorKind {
+    /// The bytes are not an operation, or are cut off.
+    InvalidOperation,
+    /// An operation the runtime does not execute.
+    UnsupportedOperation(ByteOp),
+    JumpOutOfRange {
+        target: usize,
+    },
+    JumpIntoOperation {
+        target: usize,
+    },
+    ConstantOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    VariableOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    BuiltinOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    /// An import whose path constant is not a string.
+    InvalidImportPath {
+        index: usize,
+    },
+    /// A scope load naming a code object this code is not nested in.
+    UnknownScope {
+        id: usize,
+    },
+    /// A nonlocal load not directly after the scope load it reads from.
+    MissingScope,
+    InvalidComparison {
+        operand: usize,
+    },
+    InvalidFlag {
+        operand: usize,
+    },
+    /// A map built from an odd number of values, leaving a key without its value.
+    OddMapEntries {
+        operand: usize,
+    },
+    /// A function parameter the body has no variable to bind to.
+    UnknownParameter {
+        name: String,
+    },
+    /// A method without the parameter its instance is bound to.
+    MethodWithoutSelf,
+    /// The operation pops more values than the paths reaching it leave.
+    StackUnderflow {
+        depth: usize,
+        pops: usize,
+    },
+    /// Paths reaching the operation leave different numbers of values.
+    StackMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}: ", self.code_object, self.offset)?;
+        match &self.kind {
+            VerifyErrorKind::InvalidOperation => write!(f, "invalid operation"),
+            VerifyErrorKind::UnsupportedOperation(op) => write!(f, "unsupported {:?}", op),
+            VerifyErrorKind::JumpOutOfRange { target } => {
+                write!(f, "jump to {} is past the end of the code", target)
+            }
+            VerifyErrorKind::JumpIntoOperation { target } => {
+                write!(f, "jump to {} lands inside an operation", target)
+            }
+            VerifyErrorKind::ConstantOutOfRange { index, len } => {
+                write!(f, "constant {} out of range ({} constants)", index, len)
+            }
+            VerifyErrorKind::VariableOutOfRange { index, len } => {
+                write!(f, "variable {} out of range ({} variables)", index, len)
+            }
+            VerifyErrorKind::BuiltinOutOfRange { index, len } => {
+                write!(f, "builtin {} out of range ({} builtins)", index, len)
+            }
+            VerifyErrorKind::InvalidImportPath { index } => {
+                write!(f, "import path constant {} is not a string", index)
+            }
+            VerifyErrorKind::UnknownScope { id } => {
+                write!(f, "scope {} is not an enclosing code object", id)
+            }
+            VerifyErrorKind::MissingScope => write!(f, "nonlocal load without a scope load"),
+            VerifyErrorKind::InvalidComparison { operand } => {
+                write!(f, "invalid comparison {}", operand)
+            }
+            VerifyErrorKind::InvalidFlag { operand } => write!(f, "invalid flag {}", operand),
+            VerifyErrorKind::OddMapEntries { operand } => {
+                write!(f, "map of {} values has a key without a value", operand)
+            }
+            VerifyErrorKind::UnknownParameter { name } => {
+                write!(f, "parameter {} is not a variable", name)
+            }
+            VerifyErrorKind::MethodWithoutSelf => write!(f, "method without a self parameter"),
+            VerifyErrorKind::StackUnderflow { depth, pops } => {
+                write!(f, "pops {} values with {} on the stack", pops, depth)
+            }
+            VerifyErrorKind::StackMismatch { expected, found } => write!(
+                f,
+                "reached with {} values on the stack, {} on another path",
+                found, expected
+            ),
+        }
+    }
+}
+
+/// Checks that the code object, and every code object nested in its constants, can be run
+/// without the runtime indexing out of bounds or popping an empty stack: operations decode,
+/// jumps land on operations, operands index what they refer to, every path reaching an
+/// operation leaves the same number of values on the stack, and functions can bind their
+/// parameters.
+pub fn verify(code_object: &CodeObject) -> Result<(), VerifyError> {
+    verify_nested(code_object, &mut Vec::new())
+}
+
+/// `enclosing` holds the id and variable count of the code object and those it is nested in,
+/// the scopes its nonlocal loads can read.
+fn verify_nested(
+    code_object: &CodeObject,
+    enclosing: &mut Vec<(usize, usize)>,
+) -> Result<(), VerifyError> {
+    enclosing.push((code_object.id, code_object.variables.len()));
+    let operations = verify_operands(code_object, enclosing)?;
+    verify_stack(code_object, &operations)?;
+    for constant in code_object.constants.iter() {
+        match &*constant.borrow() {
+            Value::Function(function) => {
+                verify_parameters(function)?;
+                verify_nested(&function.body, enclosing)?
+            }
+            Value::Method(method) => {
+                verify_parameters(&method.function)?;
+                if method.function.parameters.is_empty() {
+                    return Err(VerifyError {
+                        code_object: method.function.body.name.clone(),
+                        offset: 0,
+                        kind: VerifyErrorKind::MethodWithoutSelf,
+                    });
+                }
+                verify_nested(&method.function.body, enclosing)?
+            }
+            Value::Class(class) => verify_nested(&class.body, enclosing)?,
+            _ => {}
+        }
+    }
+    enclosing.pop();
+    Ok(())
+}
+
+/// Checks that calls can store every argument into a variable of the body.
+fn verify_parameters(function: &FunctionValue) -> Result<(), VerifyError> {
+    match function
+        .parameters
+        .iter()
+        .find(|parameter| !function.body.variable_index_lookup.contains_key(*parameter))
+    {
+        Some(parameter) => Err(VerifyError {
+            code_object: function.body.name.clone(),
+            offset: 0,
+            kind: VerifyErrorKind::UnknownParameter {
+                name: parameter.clone(),
+            },
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Decodes the code, checking each operand on its own, and returns the operations with the
+/// offsets they start at.
+fn verify_operands(
+    code_object: &CodeObject,
+    enclosing: &[(usize, usize)],
+) -> Result<Vec<(usize, OpIndex)>, VerifyError> {
+    let error = |offset: usize, kind: VerifyErrorKind| VerifyError {
+        code_object: code_object.name.clone(),
+        offset,
+        kind,
+    };
+    let mut operations = Vec::new();
+    let mut ip = 0;
+    while ip < code_object.code.len() {
+        let offset = ip;
+        let op = OpIndex::decode(&code_object.code, &mut ip)
+            .ok_or_else(|| error(offset, VerifyErrorKind::InvalidOperation))?;
+        operations.push((offset, op));
+    }
+
+    let constants = code_object.constants.len();
+    let variables = code_object.variables.len();
+    // variable count of the scope the previous operation loaded, if it was a scope load
+    let mut scope: Option<usize> = None;
+    for (offset, op) in operations.iter() {
+        let index = op.operand;
+        let kind = match op.operation {
+            ByteOp::LoadConstant if index >= constants => {
+                Some(VerifyErrorKind::ConstantOutOfRange {
+                    index,
+                    len: constants,
+                })
+            }
+            ByteOp::Import if index >= constants => Some(VerifyErrorKind::ConstantOutOfRange {
+                index,
+                len: constants,
+            }),
+            ByteOp::Import
+                if !matches!(&*code_object.constants[index].borrow(), Value::String(_)) =>
+            {
+                Some(VerifyErrorKind::InvalidImportPath { index })
+            }
+            ByteOp::LoadLocal | ByteOp::LoadReference | ByteOp::PreAssign if index >= variables => {
+                Some(VerifyErrorKind::VariableOutOfRange {
+                    index,
+                    len: variables,
+                })
+            }
+            ByteOp::LoadBuiltin if index >= BUILTINS.len() => {
+                Some(VerifyErrorKind::BuiltinOutOfRange {
+                    index,
+                    len: BUILTINS.len(),
+                })
+            }
+            ByteOp::LoadScope => match enclosing.iter().find(|(id, _)| *id == index) {
+                Some((_, scope_variables)) => {
+                    scope = Some(*scope_variables);
+                    continue;
+                }
+                None => Some(VerifyErrorKind::UnknownScope { id: index }),
+            },
+            ByteOp::LoadNonlocal | ByteOp::LoadNonlocalReference => match scope {
+                None => Some(VerifyErrorKind::MissingScope),
+                Some(scope_variables) if index >= scope_variables => {
+                    Some(VerifyErrorKind::VariableOutOfRange {
+                        index,
+                        len: scope_variables,
+                    })
+                }
+                Some(_) => None,
+            },
+            ByteOp::Compare => ByteComparisonOp::try_from(index)
+                .err()
+                .map(|operand| VerifyErrorKind::InvalidComparison { operand }),
+            ByteOp::MakeMap if index % 2 == 1 => {
+                Some(VerifyErrorKind::OddMapEntries { operand: index })
+            }
+            ByteOp::Contains | ByteOp::MakeClass if index > 1 => {
+                Some(VerifyErrorKind::InvalidFlag { operand: index })
+            }
+            ByteOp::Jump | ByteOp::PopJumpIfFalse => {
+                if index > code_object.code.len() {
+                    Some(VerifyErrorKind::JumpOutOfRange { target: index })
+                } else if index < code_object.code.len()
+                    && operations
+                        .binary_search_by_key(&index, |(offset, _)| *offset)
+                        .is_err()
+                {
+                    Some(VerifyErrorKind::JumpIntoOperation { target: index })
+                } else {
+                    None
+                }
+            }
+            ByteOp::PostAssign | ByteOp::Negate | ByteOp::Not => {
+                Some(VerifyErrorKind::UnsupportedOperation(op.operation))
+            }
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            return Err(error(*offset, kind));
+        }
+        scope = None;
+    }
+    Ok(operations)
+}
+
+/// How many values the operation pops, and how many it pushes after.
+fn stack_effect(op: &OpIndex) -> (usize, usize) {
+    match op.operation {
+        ByteOp::LoadConstant
+        | ByteOp::LoadLocal
+        | ByteOp::LoadScope
+        | ByteOp::LoadNull
+        | ByteOp::LoadBuiltin
+        | ByteOp::LoadReference
+        | ByteOp::Import => (0, 1),
+        ByteOp::LoadNonlocal
+        | ByteOp::LoadNonlocalReference
+        | ByteOp::Deref
+        | ByteOp::Negate
+        | ByteOp::Not
+        | ByteOp::MatchSequence
+        | ByteOp::MatchMapping
+        | ByteOp::LoadMatchArg => (1, 1),
+        ByteOp::MakeList | ByteOp::MakeTuple | ByteOp::MakeMap => (op.operand, 1),
+        ByteOp::MakeClass => (1 + op.operand, 1),
+        ByteOp::MakeSlice => (3, 1),
+        ByteOp::UnpackSequence => (1, op.operand),
+        // the container being built stays below
+        ByteOp::ListAppend | ByteOp::ListExtend | ByteOp::MapUpdate => (2, 1),
+        ByteOp::MapInsert => (3, 1),
+        ByteOp::Add
+        | ByteOp::Sub
+        | ByteOp::Mul
+        | ByteOp::Div
+        | ByteOp::IntDiv
+        | ByteOp::Mod
+        | ByteOp::Exp
+        | ByteOp::Compare
+        | ByteOp::Contains
+        | ByteOp::MatchClass
+        | ByteOp::LogicalAnd
+        | ByteOp::LogicalOr
+        | ByteOp::BinarySubscribe
+        | ByteOp::AccessAttribute
+        | ByteOp::CallSpread => (2, 1),
+        ByteOp::PreAssign | ByteOp::PostAssign | ByteOp::Pop | ByteOp::PopJumpIfFalse => (1, 0),
+        ByteOp::AssignSubscribe | ByteOp::AssignAttribute => (3, 0),
+        ByteOp::StoreDeref => (2, 0),
+        ByteOp::Call | ByteOp::TailCall => (op.operand + 1, 1),
+        // the result is whatever is on top, if anything
+        ByteOp::ReturnValue | ByteOp::Jump => (0, 0),
+    }
+}
+
+/// Follows every path through the code, checking the stack depth each leaves at each operation.
+fn verify_stack(
+    code_object: &CodeObject,
+    operations: &[(usize, OpIndex)],
+) -> Result<(), VerifyError> {
+    let error = |offset: usize, kind: VerifyErrorKind| VerifyError {
+        code_object: code_object.name.clone(),
+        offset,
+        kind,
+    };
+    // depth on entry to each operation, by position in `operations`; the end is one past
+    let mut depths: Vec<Option<usize>> = vec![None; operations.len() + 1];
+    let position = |offset: usize| {
+        operations
+            .binary_search_by_key(&offset, |(start, _)| *start)
+            .unwrap_or(operations.len())
+    };
+    let mut pending = vec![(0, 0)];
+    while let Some((i, depth)) = pending.pop() {
+        let offset = operations
+            .get(i)
+            .map_or(code_object.code.len(), |(offset, _)| *offset);
+        match depths[i] {
+            Some(expected) if expected != depth => {
+                return Err(error(
+                    offset,
+                    VerifyErrorKind::StackMismatch {
+                        expected,
+                        found: depth,
+                    },
+                ));
+            }
+            Some(_) => continue,
+            None => depths[i] = Some(depth),
+        }
+        let Some((_, op)) = operations.get(i) else {
+            continue;
+        };
+        let (pops, pushes) = stack_effect(op);
+        if pops > depth {
+            return Err(error(
+                offset,
+                VerifyErrorKind::StackUnderflow { depth, pops },
+            ));
+        }
+        let after = depth - pops + pushes;
+        match op.operation {
+            ByteOp::ReturnValue => {}
+            ByteOp::Jump => pending.push((position(op.operand), after)),
+            ByteOp::PopJumpIfFalse => {
+                pending.extend([(i + 1, after), (position(op.operand), after)])
+            }
+            _ => pending.push((i + 1, after)),
+        }
+    }
+    Ok(())
+}
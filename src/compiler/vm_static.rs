@@ -30,3 +30,27 @@ pub(crate) fn cache_variable(code_object: &mut CodeObject, name: &String) -> usi
         new_variable_index
     }
 }
+
+/// Hidden local variables holding intermediate values, e.g. the values a match is destructuring.
+/// Their names can't collide with identifiers, so they never shadow user variables.
+pub(crate) struct HiddenSlots {
+    prefix: &'static str,
+    owner_id: usize,
+    count: usize,
+}
+
+impl HiddenSlots {
+    pub(crate) fn new(prefix: &'static str, owner_id: usize) -> Self {
+        Self {
+            prefix,
+            owner_id,
+            count: 0,
+        }
+    }
+
+    pub(crate) fn next(&mut self, code_object: &Rc<RefCell<CodeObject>>) -> usize {
+        let name = format!("${}{}.{}", self.prefix, self.owner_id, self.count);
+        self.count += 1;
+        cache_variable(&mut code_object.borrow_mut(), &name)
+    }
+}
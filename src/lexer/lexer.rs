@@ -4,6 +4,7 @@ use std::collections::HashMap;
 pub struct Lexer<'a> {
     chars: std::str::Chars<'a>,
     current_char: Option<char>,
+    line: usize,
     reserved_keywords: HashMap<&'static str, token::TokenKind>,
     single_char_tokens: HashMap<char, token::TokenKind>,
     special_token_handlers: HashMap<char, fn(&mut Self) -> token::Token>,
@@ -19,6 +20,10 @@ impl<'a> Lexer<'a> {
             ("true", token::TokenKind::True),
             ("false", token::TokenKind::False),
             ("while", token::TokenKind::While),
+            ("match", token::TokenKind::Match),
+            ("import", token::TokenKind::Import),
+            ("from", token::TokenKind::From),
+            ("as", token::TokenKind::As),
             ("break", token::TokenKind::Break),
             ("continue", token::TokenKind::Continue),
             ("fn", token::TokenKind::Function),
@@ -27,6 +32,8 @@ impl<'a> Lexer<'a> {
             ("init", token::TokenKind::Initializer),
             ("and", token::TokenKind::LogicalAND),
             ("or", token::TokenKind::LogicalOR),
+            ("not", token::TokenKind::LogicalNOT),
+            ("in", token::TokenKind::In),
             ("null", token::TokenKind::Null),
         ]);
         let single_char_tokens = HashMap::from([
@@ -39,7 +46,6 @@ impl<'a> Lexer<'a> {
             (':', token::TokenKind::Colon),
             (';', token::TokenKind::Semicolon),
             (',', token::TokenKind::Comma),
-            ('.', token::TokenKind::Dot),
         ]);
         let special_tokenizers = HashMap::from([
             ('+', Lexer::plus_token as fn(&mut Self) -> token::Token), // implies cast on all v
@@ -49,13 +55,16 @@ impl<'a> Lexer<'a> {
             ('%', Lexer::modulo_token),
             ('=', Lexer::equal_token),
             ('&', Lexer::ampersand_token),
+            ('|', Lexer::pipe_token),
             ('!', Lexer::exclamation_token),
             ('>', Lexer::greater_than_token),
             ('<', Lexer::less_than_token),
+            ('.', Lexer::dot_token),
         ]);
         Self {
             chars,
             current_char,
+            line: 1,
             reserved_keywords,
             single_char_tokens,
             special_token_handlers: special_tokenizers,
@@ -63,6 +72,9 @@ impl<'a> Lexer<'a> {
     }
 
     pub fn advance(&mut self) {
+        if self.current_char == Some('\n') {
+            self.line += 1;
+        }
         self.current_char = self.chars.next();
     }
 
@@ -227,6 +239,10 @@ impl<'a> Lexer<'a> {
                 self.advance();
                 token::Token::new(token::TokenKind::Equals, "==".to_string())
             }
+            Some('>') => {
+                self.advance();
+                token::Token::new(token::TokenKind::FatArrow, "=>".to_string())
+            }
             _ => token::Token::new(token::TokenKind::Assign, "=".to_string()),
         }
     }
@@ -242,6 +258,17 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    fn pipe_token(&mut self) -> token::Token {
+        self.advance();
+        match self.current_char {
+            Some('|') => {
+                self.advance();
+                token::Token::new(token::TokenKind::LogicalOR, "||".to_string())
+            }
+            _ => token::Token::new(token::TokenKind::Pipe, "|".to_string()),
+        }
+    }
+
     fn exclamation_token(&mut self) -> token::Token {
         self.advance();
         match self.current_char {
@@ -275,7 +302,26 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    fn dot_token(&mut self) -> token::Token {
+        self.advance();
+        if self.current_char == Some('.') && self.chars.clone().next() == Some('.') {
+            self.advance();
+            self.advance();
+            return token::Token::new(token::TokenKind::Spread, "...".to_string());
+        }
+        token::Token::new(token::TokenKind::Dot, ".".to_string())
+    }
+
     pub fn next(&mut self) -> token::Token {
+        self.skip_whitespace();
+        let line = self.line;
+        token::Token {
+            line,
+            ..self.scan_token()
+        }
+    }
+
+    fn scan_token(&mut self) -> token::Token {
         while let Some(c) = self.current_char {
             if c.is_whitespace() {
                 self.skip_whitespace();
@@ -14,6 +14,9 @@ pub enum TokenKind {
     GreaterThan,
     GreaterThanEquals,
 
+    In,
+    NotIn,
+
     LogicalAND,
     LogicalOR,
     LogicalNOT,
@@ -50,6 +53,7 @@ pub enum TokenKind {
     True,
     False,
     While,
+    Match,
     Break,
     Continue,
     Function,
@@ -57,21 +61,32 @@ pub enum TokenKind {
     Class,
     Return,
     Null,
+    Import,
+    From,
+    As,
 
     Comma,
     Colon,
     Semicolon,
     Dot,
+    Spread,
     Ampersand,
+    Pipe,
+    FatArrow,
 }
 
 pub struct Token {
     pub kind: TokenKind,
     pub value: String,
+    pub line: usize, // 1-based source line the token starts on, set by the lexer
 }
 
 impl Token {
     pub fn new(kind: TokenKind, value: String) -> Token {
-        Self { kind, value }
+        Self {
+            kind,
+            value,
+            line: 0,
+        }
     }
 }
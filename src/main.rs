@@ -1,23 +1,96 @@
-use crate::compiler::Compiler;
-use crate::compiler::compiler::CompileContext;
-use crate::lexer::TokenKind;
+use crate::compiler::bytecode_file::{self, BYTECODE_EXTENSION};
+use crate::compiler::compiler::{OptimizationLevel, compile_source};
+use crate::compiler::disassembler::disassemble;
+use std::path::PathBuf;
 mod compiler;
 mod lexer;
 mod parser;
 mod runtime;
 mod utils;
 
+const DEFAULT_ENTRY: &str = "input/pik.txt";
+
 fn main() {
-    // let source: String = String::from("fn rage(arg1, arg2) {}");
-    let source = std::fs::read_to_string("input/pik.txt").unwrap();
-    let mut lex = lexer::Lexer::new(&source);
-    let mut parser = parser::Parser::new(&mut lex);
-    let ast = parser.parse(TokenKind::EOF);
-    // dbg!(&ast);
-    let mut compiler = Compiler::new();
-    let code_obj = compiler.compile(ast, &CompileContext::Normal);
+    // usage: shriky [entry file] [--path <module search dir>]... [--recursion-limit <depth>]
+    //        [--opt-level <0-2>]
+    //        shriky compile <source file> [-o <output file>] [--opt-level <0-2>]
+    //        shriky dis <source or compiled file> [--opt-level <0-2>]
+    // an entry ending in `.shrc` is run as compiled, without its source
+    let mut args = std::env::args().skip(1).peekable();
+    let compile_only = args.next_if_eq("compile").is_some();
+    let disassemble_only = !compile_only && args.next_if_eq("dis").is_some();
+    let mut output = None;
+    let mut entry = PathBuf::from(DEFAULT_ENTRY);
+    let mut search_paths = Vec::new();
+    let mut recursion_limit = None;
+    let mut optimization = OptimizationLevel::default();
+    while let Some(arg) = args.next() {
+        if arg == "--path" {
+            search_paths.push(PathBuf::from(
+                args.next().expect("--path expects a directory"),
+            ));
+        } else if arg == "--recursion-limit" {
+            recursion_limit = Some(
+                args.next()
+                    .and_then(|limit| limit.parse().ok())
+                    .expect("--recursion-limit expects a frame count"),
+            );
+        } else if arg == "--opt-level" {
+            optimization = args
+                .next()
+                .and_then(|level| level.parse().ok())
+                .and_then(OptimizationLevel::from_level)
+                .expect("--opt-level expects 0, 1 or 2");
+        } else if arg == "-o" {
+            output = Some(PathBuf::from(args.next().expect("-o expects a file")));
+        } else {
+            entry = PathBuf::from(arg);
+        }
+    }
+    if let Ok(env_paths) = std::env::var("SHRIKY_PATH") {
+        search_paths.extend(std::env::split_paths(&env_paths));
+    }
+
+    let code_obj = if entry
+        .extension()
+        .is_some_and(|extension| extension == BYTECODE_EXTENSION)
+    {
+        let bytes = std::fs::read(&entry).unwrap();
+        bytecode_file::load(&bytes).unwrap_or_else(|err| {
+            eprintln!("Cannot load {}: {}", entry.display(), err);
+            std::process::exit(1);
+        })
+    } else {
+        let source = std::fs::read_to_string(&entry).unwrap();
+        compile_source(&source, &entry, optimization)
+    };
+    if compile_only {
+        let output = output.unwrap_or_else(|| entry.with_extension(BYTECODE_EXTENSION));
+        std::fs::write(&output, bytecode_file::save(&code_obj)).unwrap();
+        return;
+    }
+    if disassemble_only {
+        print!("{}", disassemble(&code_obj));
+        return;
+    }
     let mut runtime = runtime::Runtime::new();
+    // modules resolve relative to the entry file first
+    runtime.add_search_path(
+        entry
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(".")),
+    );
+    search_paths
+        .into_iter()
+        .for_each(|path| runtime.add_search_path(path));
+    if let Some(limit) = recursion_limit {
+        runtime.set_recursion_limit(limit);
+    }
+    runtime.set_optimization_level(optimization);
     // runtime.print_current_stack_status(code_obj.clone());
     // println!("{:?}", code_obj.operations);
-    runtime.run(&code_obj);
+    if !runtime.run(code_obj) {
+        std::process::exit(1);
+    }
 }
@@ -10,9 +10,12 @@ pub enum ExprNode {
     Bool(BoolNode),
     String(StringNode),
     Identity(IdentityNode),
+    Group(GroupNode),
     BinarySubscribe(BinarySubscribeNode),
+    Slice(SliceNode),
     AccessAttribute(AccessAttributeNode),
     Reference(ReferenceNode),
+    Deref(DerefNode),
     Logical(LogicalNode),
     Comparison(ComparisonNode),
     Binary(BinaryNode),
@@ -20,11 +23,16 @@ pub enum ExprNode {
     Assign(AssignNode),
     Map(MapNode),
     List(ListNode),
+    Tuple(TupleNode),
+    Spread(SpreadNode),
+    Destructure(DestructureNode),
     Function(FunctionNode),
     Class(ClassNode),
     Return(ReturnNode),
     While(WhileNode),
     If(IfNode),
+    Match(MatchNode),
+    Import(ImportNode),
     Null,
 }
 
@@ -35,9 +43,12 @@ pub enum ExprKind {
     Bool,
     String,
     Identity,
+    Group,
     AccessConstant,
+    Slice,
     AccessAttribute,
     Reference,
+    Deref,
     Logical,
     Comparison,
     Binary,
@@ -45,11 +56,16 @@ pub enum ExprKind {
     Assign,
     Object,
     List,
+    Tuple,
+    Spread,
+    Destructure,
     Function,
     Class,
     Return,
     While,
     If,
+    Match,
+    Import,
     Null,
 }
 
@@ -63,21 +79,29 @@ impl ExprNode {
             ExprNode::Bool(_) => ExprKind::Bool,
             ExprNode::String(_) => ExprKind::String,
             ExprNode::Identity(_) => ExprKind::Identity,
+            ExprNode::Group(_) => ExprKind::Group,
             ExprNode::Reference(_) => ExprKind::Reference,
+            ExprNode::Deref(_) => ExprKind::Deref,
             ExprNode::Binary(_) => ExprKind::Binary,
             ExprNode::Call(_) => ExprKind::Call,
             ExprNode::Assign(_) => ExprKind::Assign,
             ExprNode::Map(_) => ExprKind::Object,
             ExprNode::List(_) => ExprKind::List,
+            ExprNode::Tuple(_) => ExprKind::Tuple,
+            ExprNode::Spread(_) => ExprKind::Spread,
+            ExprNode::Destructure(_) => ExprKind::Destructure,
             ExprNode::Logical(_) => ExprKind::Logical,
             ExprNode::Comparison(_) => ExprKind::Comparison,
             ExprNode::BinarySubscribe(_) => ExprKind::AccessConstant,
+            ExprNode::Slice(_) => ExprKind::Slice,
             ExprNode::AccessAttribute(_) => ExprKind::AccessAttribute,
             ExprNode::Function(_) => ExprKind::Function,
             ExprNode::Class(_) => ExprKind::Class,
             ExprNode::Return(_) => ExprKind::Return,
             ExprNode::While(_) => ExprKind::While,
             ExprNode::If(_) => ExprKind::If,
+            ExprNode::Match(_) => ExprKind::Match,
+            ExprNode::Import(_) => ExprKind::Import,
             ExprNode::Null => ExprKind::Null,
         }
     }
@@ -89,6 +113,17 @@ impl ExprNode {
         )
     }
 
+    /// Whether compiling this node in a value context leaves a value on the stack.
+    pub fn produces_value(&self) -> bool {
+        !matches!(
+            self,
+            ExprNode::Assign(_)
+                | ExprNode::Destructure(_)
+                | ExprNode::While(_)
+                | ExprNode::Import(_)
+        )
+    }
+
     pub fn int(value: i64) -> ExprNode {
         ExprNode::Int(IntNode {
             id: NODE_ID_COUNTER.next(),
@@ -117,7 +152,7 @@ impl ExprNode {
         })
     }
 
-    pub fn map(properties: Vec<MapProperty>) -> ExprNode {
+    pub fn map(properties: Vec<MapEntry>) -> ExprNode {
         ExprNode::Map(MapNode {
             id: NODE_ID_COUNTER.next(),
             properties,
@@ -131,6 +166,28 @@ impl ExprNode {
         })
     }
 
+    pub fn tuple(elements: Vec<ExprNode>) -> ExprNode {
+        ExprNode::Tuple(TupleNode {
+            id: NODE_ID_COUNTER.next(),
+            elements,
+        })
+    }
+
+    pub fn spread(value: ExprNode) -> ExprNode {
+        ExprNode::Spread(SpreadNode {
+            id: NODE_ID_COUNTER.next(),
+            value: Box::new(value),
+        })
+    }
+
+    pub fn destructure(target: AssignTarget, value: ExprNode) -> ExprNode {
+        ExprNode::Destructure(DestructureNode {
+            id: NODE_ID_COUNTER.next(),
+            target,
+            value: Box::new(value),
+        })
+    }
+
     pub fn access_constant(value: ExprNode) -> ExprNode {
         ExprNode::BinarySubscribe(BinarySubscribeNode {
             id: NODE_ID_COUNTER.next(),
@@ -138,6 +195,19 @@ impl ExprNode {
         })
     }
 
+    pub fn slice(
+        start: Option<ExprNode>,
+        end: Option<ExprNode>,
+        step: Option<ExprNode>,
+    ) -> ExprNode {
+        ExprNode::Slice(SliceNode {
+            id: NODE_ID_COUNTER.next(),
+            start: start.map(Box::new),
+            end: end.map(Box::new),
+            step: step.map(Box::new),
+        })
+    }
+
     pub fn access_attribute(value: ExprNode) -> ExprNode {
         ExprNode::AccessAttribute(AccessAttributeNode {
             id: NODE_ID_COUNTER.next(),
@@ -187,6 +257,13 @@ impl ExprNode {
         })
     }
 
+    pub fn group(value: ExprNode) -> ExprNode {
+        ExprNode::Group(GroupNode {
+            id: NODE_ID_COUNTER.next(),
+            value: Box::new(value),
+        })
+    }
+
     pub fn reference(identity: IdentityNode) -> ExprNode {
         ExprNode::Reference(ReferenceNode {
             id: NODE_ID_COUNTER.next(),
@@ -194,6 +271,13 @@ impl ExprNode {
         })
     }
 
+    pub fn deref(reference: ExprNode) -> ExprNode {
+        ExprNode::Deref(DerefNode {
+            id: NODE_ID_COUNTER.next(),
+            reference: Box::new(reference),
+        })
+    }
+
     pub fn assign(identity: IdentityNode, value: ExprNode, return_after: bool) -> ExprNode {
         ExprNode::Assign(AssignNode {
             id: NODE_ID_COUNTER.next(),
@@ -203,17 +287,19 @@ impl ExprNode {
         })
     }
 
-    pub fn function(arguments: Vec<String>, body: Vec<ExprNode>) -> ExprNode {
+    pub fn function(name: String, arguments: Vec<String>, body: Vec<ExprNode>) -> ExprNode {
         ExprNode::Function(FunctionNode {
             id: NODE_ID_COUNTER.next(),
+            name,
             arguments,
             body,
         })
     }
 
-    pub fn class(parent: Option<ExprNode>, body: Vec<ExprNode>) -> ExprNode {
+    pub fn class(name: String, parent: Option<ExprNode>, body: Vec<ExprNode>) -> ExprNode {
         ExprNode::Class(ClassNode {
             id: NODE_ID_COUNTER.next(),
+            name,
             superclass: parent.map(|p| Box::new(p)),
             body,
         })
@@ -247,6 +333,23 @@ impl ExprNode {
         })
     }
 
+    pub fn match_n(subject: ExprNode, arms: Vec<MatchArm>) -> ExprNode {
+        ExprNode::Match(MatchNode {
+            id: NODE_ID_COUNTER.next(),
+            subject: Box::new(subject),
+            arms,
+        })
+    }
+
+    pub fn import(path: String, binding: String, names: Vec<String>) -> ExprNode {
+        ExprNode::Import(ImportNode {
+            id: NODE_ID_COUNTER.next(),
+            path,
+            binding,
+            names,
+        })
+    }
+
     pub fn null() -> ExprNode {
         ExprNode::Null
     }
@@ -260,21 +363,29 @@ impl HasId for ExprNode {
             ExprNode::Bool(node) => node.id,
             ExprNode::String(node) => node.id,
             ExprNode::Identity(node) => node.id,
+            ExprNode::Group(node) => node.id,
             ExprNode::Reference(node) => node.id,
+            ExprNode::Deref(node) => node.id,
             ExprNode::Binary(node) => node.id,
             ExprNode::Call(node) => node.id,
             ExprNode::Assign(node) => node.id,
             ExprNode::Map(node) => node.id,
             ExprNode::List(node) => node.id,
+            ExprNode::Tuple(node) => node.id,
+            ExprNode::Spread(node) => node.id,
+            ExprNode::Destructure(node) => node.id,
             ExprNode::Logical(node) => node.id,
             ExprNode::Comparison(node) => node.id,
             ExprNode::BinarySubscribe(node) => node.id,
+            ExprNode::Slice(node) => node.id,
             ExprNode::AccessAttribute(node) => node.id,
             ExprNode::Function(node) => node.id,
             ExprNode::Class(node) => node.id,
             ExprNode::Return(node) => node.id,
             ExprNode::While(node) => node.id,
             ExprNode::If(node) => node.id,
+            ExprNode::Match(node) => node.id,
+            ExprNode::Import(node) => node.id,
             ExprNode::Null => 0,
         }
     }
@@ -330,7 +441,14 @@ pub struct AssignNode {
 #[derive(Clone, Debug)]
 pub struct MapNode {
     pub id: usize,
-    pub properties: Vec<MapProperty>,
+    pub properties: Vec<MapEntry>,
+}
+
+/// Entry of a map literal: `"k": v`, or `...other` merging another map in.
+#[derive(Clone, Debug)]
+pub enum MapEntry {
+    Property(MapProperty),
+    Spread(ExprNode),
 }
 
 #[derive(Clone, Debug)]
@@ -345,6 +463,69 @@ pub struct ListNode {
     pub elements: Vec<ExprNode>,
 }
 
+#[derive(Clone, Debug)]
+pub struct TupleNode {
+    pub id: usize,
+    pub elements: Vec<ExprNode>,
+}
+
+/// `...value` inside a call's arguments or a list literal.
+#[derive(Clone, Debug)]
+pub struct SpreadNode {
+    pub id: usize,
+    pub value: Box<ExprNode>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DestructureNode {
+    pub id: usize,
+    pub target: AssignTarget,
+    pub value: Box<ExprNode>,
+}
+
+/// Left-hand side of a destructuring assignment: `a, b`, `[x, [y, z]]`, `{"id": id}` or `*r`.
+#[derive(Clone, Debug)]
+pub enum AssignTarget {
+    Identity(IdentityNode),
+    Sequence(Vec<AssignTarget>),
+    Map(Vec<(ExprNode, AssignTarget)>),
+    Deref(Box<ExprNode>),
+}
+
+impl AssignTarget {
+    /// Reinterprets a parsed expression as an assignment target, or says why it can't be one.
+    pub fn from_expr(expr: ExprNode) -> Result<AssignTarget, String> {
+        match expr {
+            ExprNode::Identity(identity) if identity.ends_in_call() => {
+                Err("Cannot assign to a call".to_string())
+            }
+            ExprNode::Identity(identity) => Ok(AssignTarget::Identity(identity)),
+            ExprNode::Deref(deref) => Ok(AssignTarget::Deref(deref.reference)),
+            ExprNode::Tuple(TupleNode { elements, .. })
+            | ExprNode::List(ListNode { elements, .. }) => Ok(AssignTarget::Sequence(
+                elements
+                    .into_iter()
+                    .map(AssignTarget::from_expr)
+                    .collect::<Result<_, _>>()?,
+            )),
+            ExprNode::Map(map) => Ok(AssignTarget::Map(
+                map.properties
+                    .into_iter()
+                    .map(|entry| match entry {
+                        MapEntry::Property(property) => {
+                            Ok((property.key, AssignTarget::from_expr(property.value)?))
+                        }
+                        MapEntry::Spread(_) => {
+                            Err("Spread is not a valid assignment target".to_string())
+                        }
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            _ => Err(format!("Invalid assignment target {:?}", expr)),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LogicalNode {
     pub id: usize,
@@ -367,12 +548,26 @@ pub struct BinarySubscribeNode {
     pub value: Box<ExprNode>,
 }
 
+#[derive(Clone, Debug)]
+pub struct SliceNode {
+    pub id: usize,
+    pub start: Option<Box<ExprNode>>,
+    pub end: Option<Box<ExprNode>>,
+    pub step: Option<Box<ExprNode>>,
+}
+
 #[derive(Clone, Debug)]
 pub struct AccessAttributeNode {
     pub id: usize,
     pub value: Box<ExprNode>,
 }
 
+#[derive(Clone, Debug)]
+pub struct GroupNode {
+    pub id: usize,
+    pub value: Box<ExprNode>, // literal or parenthesized expression at the head of an identity
+}
+
 #[derive(Clone, Debug)]
 pub struct IdentityNode {
     pub id: usize,
@@ -386,6 +581,11 @@ impl IdentityNode {
             address,
         }
     }
+
+    /// Whether the identity is a call's result, as in `f(1)` or `a.b()`.
+    pub fn ends_in_call(&self) -> bool {
+        matches!(self.address.last(), Some(ExprNode::Call(_)))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -394,9 +594,17 @@ pub struct ReferenceNode {
     pub identity: IdentityNode,
 }
 
+/// `*reference`, reading or (as an assignment target) writing the referenced variable.
+#[derive(Clone, Debug)]
+pub struct DerefNode {
+    pub id: usize,
+    pub reference: Box<ExprNode>,
+}
+
 #[derive(Clone, Debug)]
 pub struct FunctionNode {
     pub id: usize,
+    pub name: String,
     pub arguments: Vec<String>,
     pub body: Vec<ExprNode>,
 }
@@ -404,6 +612,7 @@ pub struct FunctionNode {
 #[derive(Clone, Debug)]
 pub struct ClassNode {
     pub id: usize,
+    pub name: String,
     pub superclass: Option<Box<ExprNode>>,
     pub body: Vec<ExprNode>,
 }
@@ -428,3 +637,42 @@ pub struct IfNode {
     pub then_body: Vec<ExprNode>,
     pub else_body: Vec<ExprNode>,
 }
+
+#[derive(Clone, Debug)]
+pub struct ImportNode {
+    pub id: usize,
+    pub path: String,
+    pub binding: String, // variable the module is bound to when no names are imported
+    pub names: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchNode {
+    pub id: usize,
+    pub subject: Box<ExprNode>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchArm {
+    pub pattern: PatternNode,
+    pub guard: Option<ExprNode>,
+    pub body: Vec<ExprNode>,
+}
+
+#[derive(Clone, Debug)]
+pub enum PatternNode {
+    Wildcard,
+    Binding(String),
+    Value(ExprNode),
+    Or(Vec<PatternNode>),
+    List(Vec<PatternNode>),
+    Map(Vec<MapPatternProperty>),
+    Class(IdentityNode, Vec<PatternNode>),
+}
+
+#[derive(Clone, Debug)]
+pub struct MapPatternProperty {
+    pub key: ExprNode,
+    pub pattern: PatternNode,
+}
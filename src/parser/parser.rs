@@ -2,6 +2,7 @@ use crate::lexer::Token;
 use crate::lexer::{Lexer, TokenKind};
 use crate::parser::nodes::ExprKind::Logical;
 use crate::parser::nodes::*;
+use crate::parser::traits::HasId;
 use ordered_float::OrderedFloat;
 use std::collections::{HashMap, HashSet};
 use std::mem;
@@ -12,6 +13,8 @@ pub struct Parser<'a> {
     expr_handlers: HashMap<TokenKind, for<'b> fn(&'b mut Parser<'a>) -> ExprNode>,
     assignment_token_kinds: HashSet<TokenKind>,
     augmented_assignment_to_arithmetic: HashMap<TokenKind, TokenKind>,
+    lines: HashMap<usize, usize>, // statement node id -> line it starts on
+    previous_line: usize,         // line of the last token eaten
 }
 
 impl<'a> Parser<'a> {
@@ -24,16 +27,21 @@ impl<'a> Parser<'a> {
             ),
             (TokenKind::Float, Parser::handle_float),
             (TokenKind::String, Parser::handle_string),
+            (TokenKind::Null, Parser::handle_null),
             (TokenKind::True, Parser::handle_boolean),
             (TokenKind::False, Parser::handle_boolean),
             (TokenKind::Identifier, Parser::handle_identity),
             (TokenKind::Minus, Parser::handle_minus),
             (TokenKind::Ampersand, Parser::handle_ampersand),
+            (TokenKind::Asterisk, Parser::handle_deref),
             (TokenKind::Function, Parser::handle_function),
             (TokenKind::Class, Parser::handle_class),
             (TokenKind::Initializer, Parser::handle_initializer),
             (TokenKind::If, Parser::handle_if),
             (TokenKind::While, Parser::handle_while),
+            (TokenKind::Match, Parser::handle_match),
+            (TokenKind::Import, Parser::handle_import),
+            (TokenKind::From, Parser::handle_from_import),
             (TokenKind::Return, Parser::handle_return),
             (TokenKind::LeftParen, Parser::handle_paren),
             (TokenKind::LeftBracket, Parser::handle_list),
@@ -66,17 +74,33 @@ impl<'a> Parser<'a> {
             expr_handlers,
             assignment_token_kinds,
             augmented_assignment_to_arithmetic,
+            lines: HashMap::new(),
+            previous_line: 0,
         }
     }
 
+    /// The lines the parsed statements start on, by node id.
+    pub fn into_lines(self) -> HashMap<usize, usize> {
+        self.lines
+    }
+
     fn eat(&mut self, expected_token_kind: TokenKind) {
         if (self.current_token.kind == expected_token_kind) {
+            self.previous_line = self.current_token.line;
             self.current_token = self.lexer.next();
             return;
         }
-        panic!(
+        self.syntax_error(format!(
             "Expected token type {:?}, but got {:?}",
             expected_token_kind, self.current_token.kind
+        ))
+    }
+
+    /// Stops parsing, pointing at the line of the token the parser is on.
+    fn syntax_error(&self, message: String) -> ! {
+        panic!(
+            "Syntax error on line {}: {}",
+            self.current_token.line, message
         )
     }
 
@@ -95,7 +119,7 @@ impl<'a> Parser<'a> {
     fn handle_string(&mut self) -> ExprNode {
         let node = ExprNode::string(self.current_token.value.clone());
         self.eat(TokenKind::String);
-        node
+        self.handle_postfix(node)
     }
 
     fn handle_boolean(&mut self) -> ExprNode {
@@ -115,6 +139,11 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn handle_null(&mut self) -> ExprNode {
+        self.eat(TokenKind::Null);
+        ExprNode::null()
+    }
+
     fn handle_minus(&mut self) -> ExprNode {
         self.eat(TokenKind::Minus);
         ExprNode::binary(TokenKind::Asterisk, self.expr(), ExprNode::int(-1))
@@ -123,7 +152,7 @@ impl<'a> Parser<'a> {
     fn handle_ampersand(&mut self) -> ExprNode {
         self.eat(TokenKind::Ampersand);
         let maybe_identity_node = match self.current_token.kind {
-            TokenKind::Identifier => self.expr(),
+            TokenKind::Identifier => self.handle_identity(),
             _ => panic!("Cannot reference non identifier"),
         };
         let ExprNode::Identity(identity_node) = maybe_identity_node else {
@@ -135,6 +164,11 @@ impl<'a> Parser<'a> {
         ExprNode::reference(identity_node)
     }
 
+    fn handle_deref(&mut self) -> ExprNode {
+        self.eat(TokenKind::Asterisk);
+        ExprNode::deref(self.factor())
+    }
+
     fn eat_current_token_string(&mut self) -> ExprNode {
         let token_string = ExprNode::string(self.current_token.value.clone());
         self.eat(TokenKind::Identifier);
@@ -142,11 +176,33 @@ impl<'a> Parser<'a> {
     }
 
     fn handle_identity(&mut self) -> ExprNode {
-        let mut address = vec![self.eat_current_token_string()];
-        while matches!(
-            self.current_token.kind,
-            TokenKind::Dot | TokenKind::LeftBracket | TokenKind::LeftParen
-        ) {
+        let address = vec![self.eat_current_token_string()];
+        self.handle_address(address)
+    }
+
+    /// Lets literals and parenthesized expressions be accessed like identities, e.g. `"a b".split()`.
+    fn handle_postfix(&mut self, base: ExprNode) -> ExprNode {
+        if self.current_token.kind != TokenKind::LeftParen && self.continues_address() {
+            return self.handle_address(vec![ExprNode::group(base)]);
+        }
+        base
+    }
+
+    /// Whether the current token accesses into or calls the expression before it. A `[` or
+    /// `(` starting a line begins a new statement instead, so `[x, y] = pair` on the line
+    /// after a list is not a subscript of it.
+    fn continues_address(&self) -> bool {
+        match self.current_token.kind {
+            TokenKind::Dot => true,
+            TokenKind::LeftBracket | TokenKind::LeftParen => {
+                self.current_token.line == self.previous_line
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_address(&mut self, mut address: Vec<ExprNode>) -> ExprNode {
+        while self.continues_address() {
             match self.current_token.kind {
                 TokenKind::Dot => self.push_address_access_attribute(&mut address),
                 TokenKind::LeftBracket => self.push_address_access_constant(&mut address),
@@ -172,11 +228,37 @@ impl<'a> Parser<'a> {
 
     fn push_address_access_constant(&mut self, address: &mut Vec<ExprNode>) {
         self.eat(TokenKind::LeftBracket);
-        let expr = self.expr();
+        let start = self.slice_part();
+        let expr = if self.current_token.kind == TokenKind::Colon {
+            self.eat(TokenKind::Colon);
+            let end = self.slice_part();
+            let step = if self.current_token.kind == TokenKind::Colon {
+                self.eat(TokenKind::Colon);
+                self.slice_part()
+            } else {
+                None
+            };
+            ExprNode::slice(start, end, step)
+        } else {
+            start.unwrap_or_else(|| {
+                self.syntax_error(format!(
+                    "Expected a subscript expression, but got {:?}",
+                    self.current_token.kind
+                ))
+            })
+        };
         self.eat(TokenKind::RightBracket);
         address.push(ExprNode::access_constant(expr));
     }
 
+    fn slice_part(&mut self) -> Option<ExprNode> {
+        // omitted slice bounds fall back to their defaults at runtime
+        match self.current_token.kind {
+            TokenKind::Colon | TokenKind::RightBracket => None,
+            _ => Some(self.expr()),
+        }
+    }
+
     fn push_address_func_call(&mut self, address: &mut Vec<ExprNode>) {
         self.eat(TokenKind::LeftParen);
         let args = self.get_args(TokenKind::RightParen);
@@ -186,28 +268,33 @@ impl<'a> Parser<'a> {
     }
 
     fn handle_map(&mut self) -> ExprNode {
-        let mut map_properties: Vec<MapProperty> = Vec::new();
+        let mut map_properties: Vec<MapEntry> = Vec::new();
         self.eat(TokenKind::LeftCurly);
         while self.current_token.kind != TokenKind::RightCurly {
-            let key = self.expr();
-            self.eat(TokenKind::Colon);
-            map_properties.push(MapProperty {
-                key,
-                value: self.expr(),
-            });
+            if self.current_token.kind == TokenKind::Spread {
+                self.eat(TokenKind::Spread);
+                map_properties.push(MapEntry::Spread(self.expr()));
+            } else {
+                let key = self.expr();
+                self.eat(TokenKind::Colon);
+                map_properties.push(MapEntry::Property(MapProperty {
+                    key,
+                    value: self.expr(),
+                }));
+            }
             if self.current_token.kind == TokenKind::Comma {
                 self.eat(TokenKind::Comma);
             }
         }
         self.eat(TokenKind::RightCurly);
-        ExprNode::map(map_properties)
+        self.handle_postfix(ExprNode::map(map_properties))
     }
 
     fn handle_list(&mut self) -> ExprNode {
         self.eat(TokenKind::LeftBracket);
         let elements = self.get_args(TokenKind::RightBracket);
         self.eat(TokenKind::RightBracket);
-        ExprNode::list(elements)
+        self.handle_postfix(ExprNode::list(elements))
     }
 
     fn handle_increment_decrement_pre(&mut self) -> ExprNode {
@@ -244,29 +331,31 @@ impl<'a> Parser<'a> {
             .collect()
     }
 
-    fn handle_anonymous_function(&mut self) -> ExprNode {
+    fn handle_anonymous_function(&mut self, name: String) -> ExprNode {
         self.eat(TokenKind::LeftParen);
         let string_args = self.parse_function_definition_args();
         self.eat(TokenKind::RightParen);
 
         self.eat(TokenKind::LeftCurly);
-        let func_node = ExprNode::function(string_args, self.parse(TokenKind::RightCurly));
+        let func_node = ExprNode::function(name, string_args, self.parse(TokenKind::RightCurly));
         self.eat(TokenKind::RightCurly);
         func_node
     }
 
     fn handle_initializer(&mut self) -> ExprNode {
-        let func_name = ExprNode::string(self.current_token.value.clone());
+        let name = self.current_token.value.clone();
+        let func_name = ExprNode::string(name.clone());
         self.eat(TokenKind::Initializer);
-        let func_node = self.handle_anonymous_function();
+        let func_node = self.handle_anonymous_function(name);
 
         ExprNode::assign(IdentityNode::new(vec![func_name]), func_node, true)
     }
 
     fn handle_function(&mut self) -> ExprNode {
         self.eat(TokenKind::Function);
+        let name = self.current_token.value.clone();
         let func_name = self.eat_current_token_string();
-        let func_node = self.handle_anonymous_function();
+        let func_node = self.handle_anonymous_function(name);
 
         ExprNode::assign(IdentityNode::new(vec![func_name]), func_node, true)
     }
@@ -274,6 +363,7 @@ impl<'a> Parser<'a> {
     fn handle_class(&mut self) -> ExprNode {
         self.eat(TokenKind::Class);
 
+        let name = self.current_token.value.clone();
         let class_name = self.eat_current_token_string();
 
         let superclass = if self.current_token.kind == TokenKind::LeftParen {
@@ -291,7 +381,7 @@ impl<'a> Parser<'a> {
 
         ExprNode::assign(
             IdentityNode::new(vec![class_name]),
-            ExprNode::class(superclass, class_body),
+            ExprNode::class(name, superclass, class_body),
             true,
         )
     }
@@ -301,7 +391,7 @@ impl<'a> Parser<'a> {
         let return_value = if self.current_token.kind == TokenKind::Semicolon {
             ExprNode::null()
         } else {
-            self.expr()
+            self.expr_list()
         };
         ExprNode::return_n(return_value)
     }
@@ -345,53 +435,319 @@ impl<'a> Parser<'a> {
         ExprNode::while_n(condition, body)
     }
 
-    fn handle_assign(&mut self, node: ExprNode) -> ExprNode {
-        let expr_node = node.clone();
-        let ExprNode::Identity(identity) = node else {
-            panic!("Invalid assignment type {:?}", self.current_token.kind)
-        };
-        let assignment_type = self.current_token.kind;
-        self.eat(assignment_type);
+    fn eat_import_path(&mut self) -> String {
+        let path = self.current_token.value.clone();
+        self.eat(TokenKind::String);
+        path
+    }
 
-        let mut value_node = self.expr();
-        value_node = if assignment_type == TokenKind::Assign {
-            value_node
+    fn handle_import(&mut self) -> ExprNode {
+        self.eat(TokenKind::Import);
+        let path = self.eat_import_path();
+        let binding = if self.current_token.kind == TokenKind::As {
+            self.eat(TokenKind::As);
+            let alias = self.current_token.value.clone();
+            self.eat(TokenKind::Identifier);
+            alias
         } else {
-            match self
-                .augmented_assignment_to_arithmetic
-                .get(&assignment_type)
-            {
-                Some(arithmetic) => ExprNode::binary(*arithmetic, expr_node, value_node),
-                _ => panic!("Invalid assignment type {:?}", assignment_type),
+            // `import "lib/util"` binds the module to `util`
+            std::path::Path::new(&path)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_else(|| panic!("Cannot derive a module name from {:?}", path))
+                .to_string()
+        };
+        ExprNode::import(path, binding, Vec::new())
+    }
+
+    fn handle_from_import(&mut self) -> ExprNode {
+        self.eat(TokenKind::From);
+        let path = self.eat_import_path();
+        self.eat(TokenKind::Import);
+        let mut names = vec![self.current_token.value.clone()];
+        self.eat(TokenKind::Identifier);
+        while self.current_token.kind == TokenKind::Comma {
+            self.eat(TokenKind::Comma);
+            names.push(self.current_token.value.clone());
+            self.eat(TokenKind::Identifier);
+        }
+        ExprNode::import(path, String::new(), names)
+    }
+
+    fn handle_match(&mut self) -> ExprNode {
+        self.eat(TokenKind::Match);
+        let subject = self.expr();
+        self.eat(TokenKind::LeftCurly);
+        let mut arms = Vec::new();
+        while self.current_token.kind != TokenKind::RightCurly {
+            arms.push(self.parse_match_arm());
+            if self.current_token.kind == TokenKind::Comma {
+                self.eat(TokenKind::Comma);
             }
+        }
+        self.eat(TokenKind::RightCurly);
+        ExprNode::match_n(subject, arms)
+    }
+
+    fn parse_match_arm(&mut self) -> MatchArm {
+        let pattern = self.parse_pattern();
+        let guard = if self.current_token.kind == TokenKind::If {
+            self.eat(TokenKind::If);
+            Some(self.expr())
+        } else {
+            None
+        };
+        self.eat(TokenKind::FatArrow);
+        // a curly brace after the arrow always opens a block, not a map literal
+        let body = if self.current_token.kind == TokenKind::LeftCurly {
+            self.eat(TokenKind::LeftCurly);
+            let body = self.parse(TokenKind::RightCurly);
+            self.eat(TokenKind::RightCurly);
+            body
+        } else {
+            vec![self.expr()]
         };
-        ExprNode::assign(identity, value_node, true)
+        MatchArm {
+            pattern,
+            guard,
+            body,
+        }
+    }
+
+    fn parse_pattern(&mut self) -> PatternNode {
+        let mut alternatives = vec![self.parse_pattern_atom()];
+        while self.current_token.kind == TokenKind::Pipe {
+            self.eat(TokenKind::Pipe);
+            alternatives.push(self.parse_pattern_atom());
+        }
+        if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            PatternNode::Or(alternatives)
+        }
+    }
+
+    fn parse_pattern_list(&mut self, closing: TokenKind) -> Vec<PatternNode> {
+        let mut patterns = Vec::new();
+        while self.current_token.kind != closing {
+            patterns.push(self.parse_pattern());
+            if self.current_token.kind == TokenKind::Comma {
+                self.eat(TokenKind::Comma);
+            }
+        }
+        patterns
+    }
+
+    fn parse_pattern_atom(&mut self) -> PatternNode {
+        match self.current_token.kind {
+            TokenKind::Identifier => self.parse_identifier_pattern(),
+            TokenKind::LeftBracket => {
+                self.eat(TokenKind::LeftBracket);
+                let elements = self.parse_pattern_list(TokenKind::RightBracket);
+                self.eat(TokenKind::RightBracket);
+                PatternNode::List(elements)
+            }
+            TokenKind::LeftCurly => {
+                self.eat(TokenKind::LeftCurly);
+                let mut properties = Vec::new();
+                while self.current_token.kind != TokenKind::RightCurly {
+                    let key = self.parse_literal_pattern();
+                    self.eat(TokenKind::Colon);
+                    properties.push(MapPatternProperty {
+                        key,
+                        pattern: self.parse_pattern(),
+                    });
+                    if self.current_token.kind == TokenKind::Comma {
+                        self.eat(TokenKind::Comma);
+                    }
+                }
+                self.eat(TokenKind::RightCurly);
+                PatternNode::Map(properties)
+            }
+            _ => PatternNode::Value(self.parse_literal_pattern()),
+        }
+    }
+
+    fn parse_literal_pattern(&mut self) -> ExprNode {
+        match self.current_token.kind {
+            TokenKind::Minus => {
+                self.eat(TokenKind::Minus);
+                match self.factor() {
+                    ExprNode::Int(int_node) => ExprNode::int(-int_node.value),
+                    ExprNode::Float(float_node) => ExprNode::float(-float_node.value),
+                    node => panic!("Cannot negate pattern {:?}", node),
+                }
+            }
+            TokenKind::Int
+            | TokenKind::Float
+            | TokenKind::String
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::Null => self.factor(),
+            _ => panic!("Unexpected token in pattern: {:?}", self.current_token.kind),
+        }
+    }
+
+    fn parse_identifier_pattern(&mut self) -> PatternNode {
+        let name = self.current_token.value.clone();
+        let mut address = vec![self.eat_current_token_string()];
+        while self.current_token.kind == TokenKind::Dot {
+            self.eat(TokenKind::Dot);
+            address.push(ExprNode::access_attribute(self.eat_current_token_string()));
+        }
+        if self.current_token.kind == TokenKind::LeftParen {
+            self.eat(TokenKind::LeftParen);
+            let arguments = self.parse_pattern_list(TokenKind::RightParen);
+            self.eat(TokenKind::RightParen);
+            return PatternNode::Class(IdentityNode::new(address), arguments);
+        }
+        match (address.len(), name.as_str()) {
+            (1, "_") => PatternNode::Wildcard,
+            (1, _) => PatternNode::Binding(name),
+            // dotted names are compared against, like constants
+            _ => PatternNode::Value(ExprNode::identity(address)),
+        }
+    }
+
+    /// An assignment to `node`, its value parsed by `value`.
+    fn handle_assign(&mut self, node: ExprNode, value: fn(&mut Self) -> ExprNode) -> ExprNode {
+        if matches!(
+            node,
+            ExprNode::Tuple(_) | ExprNode::List(_) | ExprNode::Map(_)
+        ) {
+            return self.handle_destructure(node);
+        }
+        let target = self.assign_target(node.clone());
+        let assignment_type = self.current_token.kind;
+        self.eat(assignment_type);
+        let value_node = value(self);
+        let value_node = self.assigned_value(assignment_type, node, value_node);
+        match target {
+            AssignTarget::Identity(identity) => ExprNode::assign(identity, value_node, true),
+            // `*r = v` writes through the reference
+            target => ExprNode::destructure(target, value_node),
+        }
+    }
+
+    /// The value stored by an assignment; `a += b` stores `a + b`.
+    fn assigned_value(
+        &self,
+        assignment_type: TokenKind,
+        target: ExprNode,
+        value_node: ExprNode,
+    ) -> ExprNode {
+        if assignment_type == TokenKind::Assign {
+            return value_node;
+        }
+        match self
+            .augmented_assignment_to_arithmetic
+            .get(&assignment_type)
+        {
+            Some(arithmetic) => ExprNode::binary(*arithmetic, target, value_node),
+            _ => panic!("Invalid assignment type {:?}", assignment_type),
+        }
+    }
+
+    fn handle_destructure(&mut self, target: ExprNode) -> ExprNode {
+        let target = self.assign_target(target);
+        self.eat(TokenKind::Assign);
+        let value = self.expr_list();
+        ExprNode::destructure(target, value)
+    }
+
+    fn assign_target(&self, target: ExprNode) -> AssignTarget {
+        AssignTarget::from_expr(target).unwrap_or_else(|message| self.syntax_error(message))
     }
 
     fn handle_paren(&mut self) -> ExprNode {
         self.eat(TokenKind::LeftParen);
+        if self.current_token.kind == TokenKind::RightParen {
+            self.eat(TokenKind::RightParen);
+            return self.handle_postfix(ExprNode::tuple(Vec::new()));
+        }
         let expr = self.expr();
+        if self.current_token.kind != TokenKind::Comma {
+            self.eat(TokenKind::RightParen);
+            return self.handle_postfix(expr);
+        }
+        // `(a,)` and `(a, b)` are tuples
+        let mut elements = vec![expr];
+        while self.current_token.kind == TokenKind::Comma {
+            self.eat(TokenKind::Comma);
+            if self.current_token.kind == TokenKind::RightParen {
+                break;
+            }
+            elements.push(self.expr());
+        }
         self.eat(TokenKind::RightParen);
-        expr
+        self.handle_postfix(ExprNode::tuple(elements))
+    }
+
+    /// An expression, or a parenthesis-free tuple of them: `b, a`.
+    fn expr_list(&mut self) -> ExprNode {
+        let first = self.expr();
+        if self.current_token.kind != TokenKind::Comma {
+            return first;
+        }
+        let mut elements = vec![first];
+        while self.current_token.kind == TokenKind::Comma {
+            self.eat(TokenKind::Comma);
+            elements.push(self.expr());
+        }
+        ExprNode::tuple(elements)
+    }
+
+    /// A statement; `a, b = b, a` is only recognized here, as commas separate arguments elsewhere.
+    fn statement(&mut self) -> ExprNode {
+        let first = self.logical_or();
+        if self
+            .assignment_token_kinds
+            .contains(&self.current_token.kind)
+        {
+            // the value of a statement-level assignment may be a tuple too: `x = 1, 2`
+            return self.handle_assign(first, Self::expr_list);
+        }
+        if self.current_token.kind != TokenKind::Comma {
+            return first;
+        }
+        let mut targets = vec![first];
+        while self.current_token.kind == TokenKind::Comma {
+            self.eat(TokenKind::Comma);
+            targets.push(self.logical_or());
+        }
+        let tuple = ExprNode::tuple(targets);
+        if self.current_token.kind == TokenKind::Assign {
+            return self.handle_destructure(tuple);
+        }
+        tuple
     }
 
     fn get_args(&mut self, closing: TokenKind) -> Vec<ExprNode> {
         let mut args: Vec<ExprNode> = Vec::new();
         if self.current_token.kind != closing {
-            args.push(self.expr());
+            args.push(self.arg());
             while self.current_token.kind == TokenKind::Comma {
                 self.eat(TokenKind::Comma);
-                args.push(self.expr());
+                args.push(self.arg());
             }
         }
         args
     }
 
+    /// A call argument or list element, which may be spread: `...args`.
+    fn arg(&mut self) -> ExprNode {
+        if self.current_token.kind == TokenKind::Spread {
+            self.eat(TokenKind::Spread);
+            return ExprNode::spread(self.expr());
+        }
+        self.expr()
+    }
+
     fn factor(&mut self) -> ExprNode {
         if let Some(handler) = self.expr_handlers.get(&self.current_token.kind) {
             return handler(self);
         }
-        panic!("Unknown token {:?}", self.current_token.value);
+        self.syntax_error(format!("Unknown token {:?}", self.current_token.value));
     }
 
     fn exponent(&mut self) -> ExprNode {
@@ -404,12 +760,20 @@ impl<'a> Parser<'a> {
         node
     }
 
+    /// Whether the current token multiplies, divides or takes the modulo of the term before it.
+    /// A `*` starting a line begins a new statement instead, as in `*r = 5`, like the `[` and
+    /// `(` of `continues_address`.
+    fn continues_term(&self) -> bool {
+        match self.current_token.kind {
+            TokenKind::Slash | TokenKind::Modulo => true,
+            TokenKind::Asterisk => self.current_token.line == self.previous_line,
+            _ => false,
+        }
+    }
+
     fn term(&mut self) -> ExprNode {
         let mut node = self.exponent();
-        while self.current_token.kind == TokenKind::Asterisk
-            || self.current_token.kind == TokenKind::Slash
-            || self.current_token.kind == TokenKind::Modulo
-        {
+        while self.continues_term() {
             let token_kind = self.current_token.kind;
             self.eat(token_kind);
 
@@ -438,9 +802,18 @@ impl<'a> Parser<'a> {
                 | TokenKind::GreaterThanEquals
                 | TokenKind::LessThan
                 | TokenKind::LessThanEquals
+                | TokenKind::In
+                | TokenKind::LogicalNOT
         ) {
             let token_kind = self.current_token.kind;
             self.eat(token_kind);
+            let token_kind = if token_kind == TokenKind::LogicalNOT {
+                // a trailing `not` can only start a `not in` membership test
+                self.eat(TokenKind::In);
+                TokenKind::NotIn
+            } else {
+                token_kind
+            };
             node = ExprNode::comparison(token_kind, node, self.add_sub());
         }
         node
@@ -483,7 +856,7 @@ impl<'a> Parser<'a> {
             .assignment_token_kinds
             .contains(&self.current_token.kind)
         {
-            return self.handle_assign(node);
+            return self.handle_assign(node, Self::expr);
         }
         node
     }
@@ -499,7 +872,10 @@ impl<'a> Parser<'a> {
                 self.eat(TokenKind::Semicolon);
                 continue;
             }
-            ast.push(self.expr());
+            let line = self.current_token.line;
+            let statement = self.statement();
+            self.lines.insert(statement.id(), line);
+            ast.push(statement);
         }
         ast
     }
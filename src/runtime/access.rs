@@ -1,24 +1,25 @@
 use crate::runtime::Runtime;
-use crate::runtime::utils::extract_string_ref;
 use crate::runtime::value::exception;
-use crate::runtime::value::{RuntimeException, Value};
+use crate::runtime::value::{
+    MethodValue, RuntimeException, RustMethodValue, StackValue, Value, ValueRef,
+};
 
 pub(crate) fn binary_subscribe(runtime: &mut Runtime) -> Result<(), RuntimeException> {
-    let constant = runtime.mem_stack.pop().unwrap();
-    let container = runtime.mem_stack.pop().unwrap();
+    let constant = runtime.mem_stack.pop().unwrap().into_ref();
+    let container = runtime.mem_stack.pop().unwrap().into_ref();
     let mut container_ref = container.borrow_mut();
     let subscriptable_container = container_ref.subscriptable().ok_or(
         exception::TYPE.runtime("Attempted subscription to an unsubscribable type".to_string()),
     )?;
     let result = subscriptable_container.index(runtime, &constant)?;
-    runtime.mem_stack.push(result.clone());
+    runtime.mem_stack.push(StackValue::from(result));
     Ok(())
 }
 
 pub(crate) fn access_attr(runtime: &mut Runtime) -> Result<(), RuntimeException> {
-    let attr = runtime.mem_stack.pop().unwrap();
-    let container = runtime.mem_stack.pop().unwrap();
-    let attr_string = extract_string_ref(&attr);
+    let attr = runtime.mem_stack.pop().unwrap().into_ref();
+    let container = runtime.mem_stack.pop().unwrap().into_ref();
+    let attr_string = attribute_name(&attr)?;
     let result = {
         (&mut *container.borrow_mut())
             .attribute_accessible()
@@ -29,12 +30,29 @@ pub(crate) fn access_attr(runtime: &mut Runtime) -> Result<(), RuntimeException>
             .get_attr(runtime, &attr_string)?
     };
 
-    match &mut *result.borrow_mut() {
-        Value::Method(method_value) => method_value.caller = Some(container.clone()),
-        Value::RustMethod(method_value) => method_value.caller = Some(container.clone()),
-        _ => {}
-    }
+    // bind a copy, the method value found is shared by every instance of the class
+    let bound = match &*result.borrow() {
+        Value::Method(method_value) => Some(Value::Method(MethodValue {
+            caller: Some(container.clone()),
+            ..method_value.clone()
+        })),
+        Value::RustMethod(method_value) => Some(Value::RustMethod(RustMethodValue {
+            caller: Some(container.clone()),
+            ..method_value.clone()
+        })),
+        _ => None,
+    };
 
-    runtime.mem_stack.push(result);
+    runtime
+        .mem_stack
+        .push(bound.map_or(StackValue::from(result), StackValue::from));
     Ok(())
 }
+
+/// The name an attribute instruction was given, which only a corrupted file makes a non-string.
+pub(crate) fn attribute_name(attr: &ValueRef) -> Result<String, RuntimeException> {
+    match &*attr.borrow() {
+        Value::String(name) => Ok(name.0.clone()),
+        _ => Err(exception::TYPE.runtime("Attribute names must be strings".to_string())),
+    }
+}
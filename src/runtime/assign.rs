@@ -1,29 +1,41 @@
 use crate::runtime::Runtime;
-use crate::runtime::utils::extract_string_ref;
+use crate::runtime::access::attribute_name;
 use crate::runtime::value::exception;
-use crate::runtime::value::{RuntimeException, Value};
+use crate::runtime::value::{RuntimeException, SliceValue, Value, ValueRef};
 
+/// Binds the variable to the value itself: lists, maps, tuples and instances are shared with
+/// every other name or container holding them, while primitives are never mutated in place
+/// and so behave as values. `copy()` and `deepcopy()` make independent containers.
+/// See the object model section of the README.
 pub fn pre_assign(runtime: &mut Runtime, variable_index: usize) -> Result<(), RuntimeException> {
-    let frame = runtime.frames_stack.last().unwrap();
     let value = runtime.mem_stack.pop().unwrap();
-    let var = frame.variables[variable_index].clone();
-    let cloned_value = value.borrow().clone();
-    *var.borrow_mut() = cloned_value;
+    let frame = runtime.frames_stack.last().unwrap();
+    frame.variables.borrow_mut()[variable_index] = value;
     Ok(())
 }
 
 pub fn assign_subscribe(runtime: &mut Runtime) -> Result<(), RuntimeException> {
-    let value = runtime.mem_stack.pop().unwrap();
-    let key = runtime.mem_stack.pop().unwrap();
-    let container = runtime.mem_stack.pop().unwrap();
+    let value = runtime.mem_stack.pop().unwrap().into_ref();
+    let key = runtime.mem_stack.pop().unwrap().into_ref();
+    let container = runtime.mem_stack.pop().unwrap().into_ref();
+    runtime.gc.track_value(&container);
+    if let Value::Slice(slice) = &*key.borrow() {
+        return assign_slice(&container, slice, &value);
+    }
     match &mut *container.borrow_mut() {
         Value::Map(obj) => {
-            obj.properties.insert(key.borrow().clone(), value.clone());
+            obj.properties.insert(key.borrow().to_key()?, value.clone());
             Ok(())
         }
         Value::List(list) => {
             if let Value::Int(index) = key.borrow().clone() {
-                list.elements.insert(index.0 as usize, value.clone());
+                let len = list.elements.len() as i64;
+                let position = if index.0 < 0 { index.0 + len } else { index.0 };
+                if !(0..len).contains(&position) {
+                    return Err(exception::INDEX_ERROR
+                        .runtime(format!("List index {} out of range", index.0)));
+                }
+                list.elements[position as usize] = value.clone();
                 return Ok(());
             }
             Err(exception::TYPE
@@ -36,15 +48,37 @@ pub fn assign_subscribe(runtime: &mut Runtime) -> Result<(), RuntimeException> {
     }
 }
 
+fn assign_slice(
+    container: &ValueRef,
+    slice: &SliceValue,
+    value: &ValueRef,
+) -> Result<(), RuntimeException> {
+    // collect the new elements first, the value may be the container itself
+    let elements = match &*value.borrow() {
+        Value::List(list) => list.elements.clone(),
+        _ => {
+            return Err(
+                exception::TYPE.runtime("Only lists can be assigned to a slice".to_string())
+            );
+        }
+    };
+    match &mut *container.borrow_mut() {
+        Value::List(list) => list.assign_slice(slice, elements),
+        _ => {
+            Err(exception::TYPE.runtime("Slice assignment is only supported on lists".to_string()))
+        }
+    }
+}
+
 pub fn assign_attribute(runtime: &mut Runtime) -> Result<(), RuntimeException> {
-    let value = runtime.mem_stack.pop().unwrap();
-    let attr_name = runtime.mem_stack.pop().unwrap();
-    let container = runtime.mem_stack.pop().unwrap();
+    let value = runtime.mem_stack.pop().unwrap().into_ref();
+    let attr_name = runtime.mem_stack.pop().unwrap().into_ref();
+    let container = runtime.mem_stack.pop().unwrap().into_ref();
+    let attr_name = attribute_name(&attr_name)?;
+    runtime.gc.track_value(&container);
     match &mut *container.borrow_mut() {
         Value::Instance(instance_value) => {
-            instance_value
-                .attributes
-                .insert(extract_string_ref(&attr_name), value.clone());
+            instance_value.attributes.insert(attr_name, value.clone());
             Ok(())
         }
         _ => {
@@ -0,0 +1,155 @@
+use crate::compiler::code_object::CodeObject;
+use crate::compiler::disassembler::disassemble_in;
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
+use crate::runtime::value::exception;
+use crate::runtime::value::{MethodFn, RuntimeException, Value, ValueRef, arg_check};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Functions visible from every scope, resolved by the compiler when no variable matches.
+pub(crate) const BUILTINS: [(&str, MethodFn); 5] = [
+    ("copy", copy),
+    ("deepcopy", deepcopy),
+    ("gc", gc),
+    ("gc_stats", gc_stats),
+    ("dis", dis),
+];
+
+pub(crate) fn builtin_index(name: &str) -> Option<usize> {
+    BUILTINS
+        .iter()
+        .position(|(builtin_name, _)| *builtin_name == name)
+}
+
+/// New container holding the same elements, attributes or entries as the value.
+fn copy(
+    _runtime: &mut Runtime,
+    _caller: &ValueRef,
+    args: &[&ValueRef],
+) -> Result<Option<ValueRef>, RuntimeException> {
+    arg_check(args.len(), 1, "copy")?;
+    let copied = match &*args[0].borrow() {
+        Value::Instance(instance) => {
+            Value::instance(instance.class.clone(), instance.attributes.clone())
+        }
+        value => value.clone(),
+    };
+    Ok(Some(value_to_ref(copied)))
+}
+
+/// Recursive copy of lists, maps, tuples and instances; values shared inside the original,
+/// including cycles, stay shared inside the copy.
+fn deepcopy(
+    _runtime: &mut Runtime,
+    _caller: &ValueRef,
+    args: &[&ValueRef],
+) -> Result<Option<ValueRef>, RuntimeException> {
+    arg_check(args.len(), 1, "deepcopy")?;
+    Ok(Some(deep_copy_ref(args[0], &mut HashMap::new())))
+}
+
+fn deep_copy_ref(value: &ValueRef, copies: &mut HashMap<*const (), ValueRef>) -> ValueRef {
+    let key = Rc::as_ptr(value) as *const ();
+    if let Some(copied) = copies.get(&key) {
+        return copied.clone();
+    }
+    // register the copy before recursing so cycles resolve to it
+    let copied = value_to_ref(Value::Null);
+    copies.insert(key, copied.clone());
+    let copied_value = match &*value.borrow() {
+        Value::List(list) => Value::list(
+            list.elements
+                .iter()
+                .map(|element| deep_copy_ref(element, copies))
+                .collect(),
+        ),
+        Value::Tuple(tuple) => Value::tuple(
+            tuple
+                .elements
+                .iter()
+                .map(|element| deep_copy_ref(element, copies))
+                .collect(),
+        ),
+        Value::Map(map) => Value::map(
+            map.properties
+                .iter()
+                .map(|(key, value)| (key.clone(), deep_copy_ref(value, copies)))
+                .collect(),
+        ),
+        Value::Instance(instance) => Value::instance(
+            instance.class.clone(),
+            instance
+                .attributes
+                .iter()
+                .map(|(name, value)| (name.clone(), deep_copy_ref(value, copies)))
+                .collect(),
+        ),
+        other => other.clone(),
+    };
+    *copied.borrow_mut() = copied_value;
+    copied
+}
+
+/// Runs the cycle collector now and returns how many values it freed.
+fn gc(
+    runtime: &mut Runtime,
+    _caller: &ValueRef,
+    args: &[&ValueRef],
+) -> Result<Option<ValueRef>, RuntimeException> {
+    arg_check(args.len(), 0, "gc")?;
+    let collected = runtime.gc.collect();
+    Ok(Some(value_to_ref(Value::int(collected as i64))))
+}
+
+/// Cycle collector counters: runs so far, values freed in total and containers tracked.
+fn gc_stats(
+    runtime: &mut Runtime,
+    _caller: &ValueRef,
+    args: &[&ValueRef],
+) -> Result<Option<ValueRef>, RuntimeException> {
+    arg_check(args.len(), 0, "gc_stats")?;
+    let stats = [
+        ("collections", runtime.gc.collections),
+        ("collected", runtime.gc.collected),
+        ("tracked", runtime.gc.tracked()),
+    ]
+    .into_iter()
+    .map(|(name, count)| {
+        (
+            Value::string(name.to_string()),
+            value_to_ref(Value::int(count as i64)),
+        )
+    })
+    .collect();
+    Ok(Some(value_to_ref(Value::map(stats))))
+}
+
+/// Prints the disassembly of a function, method or class, and of the code nested in it.
+fn dis(
+    runtime: &mut Runtime,
+    _caller: &ValueRef,
+    args: &[&ValueRef],
+) -> Result<Option<ValueRef>, RuntimeException> {
+    arg_check(args.len(), 1, "dis")?;
+    let body = match &*args[0].borrow() {
+        Value::Function(function) => function.body.clone(),
+        Value::Method(method) => method.function.body.clone(),
+        Value::Class(class) => class.body.clone(),
+        other => {
+            return Err(exception::TYPE.runtime(format!(
+                "Cannot disassemble a '{}' value",
+                other.type_name()
+            )));
+        }
+    };
+    // the code it loads scopes from is running or has run
+    let scopes: Vec<&CodeObject> = runtime
+        .frames_stack
+        .iter()
+        .chain(runtime.frames_cache.values())
+        .map(|frame| &*frame.code_object)
+        .collect();
+    print!("{}", disassemble_in(&body, &scopes));
+    Ok(None)
+}
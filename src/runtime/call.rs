@@ -1,19 +1,21 @@
 use crate::runtime::Runtime;
-use crate::runtime::frame::RuntimeFrame;
+use crate::runtime::frame::{FrameKind, RuntimeFrame};
 use crate::runtime::make::make_instance;
+use crate::runtime::utils::value_to_ref;
 use crate::runtime::value::*;
 
 pub(crate) fn get_function_runtime_frame(
     function_value: &FunctionValue,
-    args: Vec<ValueRef>,
+    args: Vec<StackValue>,
 ) -> RuntimeFrame {
-    let mut func_runtime_frame = RuntimeFrame::from_co(&function_value.body);
+    let func_runtime_frame = RuntimeFrame::from_co(&function_value.body);
     function_value
         .parameters
         .iter()
         .zip(args.iter().rev())
         .for_each(|(p, v)| {
-            func_runtime_frame.variables[function_value.body.variable_index_lookup[p]] = v.clone();
+            func_runtime_frame.variables.borrow_mut()
+                [function_value.body.variable_index_lookup[p]] = v.clone();
         });
     func_runtime_frame
 }
@@ -32,61 +34,145 @@ pub(crate) fn expect_args_count(
 }
 
 pub(crate) fn call_rust_method(
+    runtime: &mut Runtime,
     function: MethodFn,
     caller: &ValueRef,
     args: &[&ValueRef],
 ) -> Result<Option<ValueRef>, RuntimeException> {
-    function(caller, args)
+    function(runtime, caller, args)
 }
 
-pub(crate) fn call(runtime: &mut Runtime, arg_count: usize) -> Result<(), RuntimeException> {
+/// Calls any callable value with `args` (in call order) and returns its result.
+pub(crate) fn call_value(
+    runtime: &mut Runtime,
+    callee: &ValueRef,
+    args: Vec<ValueRef>,
+) -> Result<ValueRef, RuntimeException> {
+    let arg_count = args.len();
+    runtime
+        .mem_stack
+        .extend(args.into_iter().map(StackValue::from));
+    runtime.mem_stack.push(StackValue::Object(callee.clone()));
+    let depth = runtime.frames_stack.len();
+    call(runtime, arg_count)?;
+    // script callees only had their frame entered, run it to completion
+    if runtime.frames_stack.len() > depth {
+        runtime.execute()?;
+    }
+    Ok(runtime.mem_stack.pop().unwrap().into_ref())
+}
+
+/// Calls the callee on top of the stack with the elements of the argument list below it.
+pub(crate) fn call_spread(runtime: &mut Runtime) -> Result<(), RuntimeException> {
     let callee = runtime.mem_stack.pop().unwrap();
-    let mut owned_args: Vec<ValueRef> = (0..arg_count)
+    let args = runtime.mem_stack.pop().unwrap().into_ref();
+    let args = args.borrow().iter_elements()?;
+    let arg_count = args.len();
+    runtime
+        .mem_stack
+        .extend(args.into_iter().map(StackValue::from));
+    runtime.mem_stack.push(callee);
+    call(runtime, arg_count)
+}
+
+/// Calls the callee on top of the stack with the `arg_count` arguments below it. Native methods
+/// leave their result right away, script callees get their frame entered for the dispatch loop.
+pub(crate) fn call(runtime: &mut Runtime, arg_count: usize) -> Result<(), RuntimeException> {
+    let callee = runtime.mem_stack.pop().unwrap().into_ref();
+    let owned_args: Vec<StackValue> = (0..arg_count)
         .map(|_| runtime.mem_stack.pop().unwrap())
         .collect();
-    let (frame, co) = {
-        match &*callee.borrow() {
-            Value::Function(func_value) => {
-                expect_args_count(owned_args.len(), func_value.parameters.len())?;
-                (
-                    get_function_runtime_frame(&func_value, owned_args),
-                    func_value.body.clone(),
-                ) // EXPENSIVE CLONE TEMP
-            }
-            Value::Method(method_value) => {
-                owned_args.push(
-                    method_value
-                        .caller
-                        .clone()
-                        .expect("method called without caller"),
-                );
-                expect_args_count(owned_args.len(), method_value.function.parameters.len())?;
-                (
-                    get_function_runtime_frame(&method_value.function, owned_args),
-                    method_value.function.body.clone(),
-                ) // EXPENSIVE CLONE TEMP
-            }
-            Value::RustMethod(method_value) => {
-                let arg_refs: Vec<&ValueRef> = owned_args.iter().collect();
-                let result = call_rust_method(
-                    method_value.function,
-                    &method_value.caller.clone().unwrap(),
-                    &arg_refs,
-                )?;
-                result.map(|result| runtime.mem_stack.push(result));
-                return Ok(());
-            }
-            Value::Class(_) => {
-                make_instance(runtime, callee.clone(), owned_args)?;
-                return Ok(());
-            }
-            _ => panic!(),
+    let rust_method = match &*callee.borrow() {
+        Value::RustMethod(method_value) => {
+            // builtins are not bound to a value
+            let caller = method_value
+                .caller
+                .clone()
+                .unwrap_or_else(|| value_to_ref(Value::Null));
+            Some((method_value.function, caller))
         }
+        _ => None,
     };
-    runtime.push_to_frame_stack(frame);
-    runtime.execute(&co)?;
-    runtime.pop_from_frame_stack();
-    let return_value = runtime.pop_mem_stack_value_or_null();
-    runtime.mem_stack.push(return_value);
-    Ok(())
+    if let Some((function, caller)) = rust_method {
+        // native methods may store their arguments into the container they are called on
+        if !owned_args.is_empty() {
+            runtime.gc.track_value(&caller);
+        }
+        // arguments were popped last-first
+        let owned_args: Vec<ValueRef> = owned_args
+            .into_iter()
+            .rev()
+            .map(StackValue::into_ref)
+            .collect();
+        let arg_refs: Vec<&ValueRef> = owned_args.iter().collect();
+        let result = call_rust_method(runtime, function, &caller, &arg_refs)?;
+        // every call leaves exactly one value behind
+        runtime
+            .mem_stack
+            .push(result.map_or(StackValue::Null, StackValue::from));
+        return Ok(());
+    }
+    match &*callee.borrow() {
+        Value::Function(_) | Value::Method(_) => {}
+        Value::Class(_) => return make_instance(runtime, callee.clone(), owned_args),
+        other => {
+            return Err(exception::TYPE.runtime(format!("{:?} is not callable", other)));
+        }
+    }
+    let frame = function_frame(&callee, owned_args)?;
+    runtime.push_to_frame_stack(frame)
+}
+
+/// Calls the callee on top of the stack in place of the frame returning its result, as
+/// `return f(...)` does, so tail recursion runs in constant frame stack space. Callees without
+/// a frame of their own, and frames that must stay around, get a regular `call` instead.
+pub(crate) fn tail_call(runtime: &mut Runtime, arg_count: usize) -> Result<(), RuntimeException> {
+    let current = runtime.frames_stack.last().unwrap();
+    // bodies are cached once finished, and functions defined in a frame find its variables
+    // through the frame stack
+    let replaceable = current.kind != FrameKind::Body && !current.code_object.defines_scopes();
+    let script_callee = match runtime.mem_stack.last().unwrap() {
+        StackValue::Object(callee) => {
+            matches!(&*callee.borrow(), Value::Function(_) | Value::Method(_))
+        }
+        _ => false,
+    };
+    if !replaceable || !script_callee {
+        return call(runtime, arg_count);
+    }
+    let callee = runtime.mem_stack.pop().unwrap().into_ref();
+    let owned_args: Vec<StackValue> = (0..arg_count)
+        .map(|_| runtime.mem_stack.pop().unwrap())
+        .collect();
+    let frame = function_frame(&callee, owned_args)?;
+    let finished = runtime.pop_from_frame_stack();
+    runtime.mem_stack.truncate(finished.stack_base);
+    // an init tail calling still results in its instance
+    runtime.push_to_frame_stack(RuntimeFrame {
+        kind: finished.kind,
+        ..frame
+    })
+}
+
+/// The frame a script function or method runs in, with its parameters bound to `args` (popped
+/// last-first).
+fn function_frame(
+    callee: &ValueRef,
+    mut args: Vec<StackValue>,
+) -> Result<RuntimeFrame, RuntimeException> {
+    match &*callee.borrow() {
+        Value::Function(func_value) => {
+            expect_args_count(args.len(), func_value.parameters.len())?;
+            Ok(get_function_runtime_frame(func_value, args))
+        }
+        Value::Method(method_value) => {
+            let caller = method_value.caller.clone().ok_or(
+                exception::TYPE.runtime("Methods can only be called on an instance".to_string()),
+            )?;
+            args.push(StackValue::Object(caller));
+            expect_args_count(args.len(), method_value.function.parameters.len())?;
+            Ok(get_function_runtime_frame(&method_value.function, args))
+        }
+        other => unreachable!("{:?} has no frame of its own", other),
+    }
 }
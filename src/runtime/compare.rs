@@ -1,22 +1,91 @@
 use crate::compiler::byte_operations::ByteComparisonOp;
 use crate::runtime::Runtime;
-use crate::runtime::value::RuntimeException;
+use crate::runtime::access::access_attr;
+use crate::runtime::call::call_value;
+use crate::runtime::value::exception;
 use crate::runtime::value::traits::Binary;
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::runtime::value::{RuntimeException, StackValue, Value, ValueRef};
 
 pub fn compare(runtime: &mut Runtime, comparison_operand: usize) -> Result<(), RuntimeException> {
-    let comparison = ByteComparisonOp::from(comparison_operand as u8);
+    let comparison = ByteComparisonOp::try_from(comparison_operand).expect("malformed comparison");
     let b = runtime.mem_stack.pop().unwrap();
     let a = runtime.mem_stack.pop().unwrap();
-    let result = match comparison {
-        ByteComparisonOp::Equal => a.borrow_mut().equals(&*b.borrow()),
-        ByteComparisonOp::Greater => a.borrow_mut().greater(&*b.borrow()),
-        ByteComparisonOp::GreaterEqual => a.borrow_mut().greater_equals(&*b.borrow()),
-        ByteComparisonOp::Less => a.borrow_mut().less(&*b.borrow()),
-        ByteComparisonOp::LessEqual => a.borrow_mut().less_equals(&*b.borrow()),
-        _ => panic!("Unimplemented comparison op: {:?}", comparison),
+    if let Some(result) = StackValue::compare(comparison, &a, &b) {
+        runtime.mem_stack.push(result);
+        return Ok(());
+    }
+    // `a` and `b` may be the same value (`x == x`), so compare against a copy
+    let mut a = a.to_value();
+    let result = b.with_value(|b| match comparison {
+        ByteComparisonOp::Equal => a.equals(b),
+        ByteComparisonOp::NotEqual => a.equals(b).map(|equal| Value::bool(!equal.is_truthy())),
+        ByteComparisonOp::Greater => a.greater(b),
+        ByteComparisonOp::GreaterEqual => a.greater_equals(b),
+        ByteComparisonOp::Less => a.less(b),
+        ByteComparisonOp::LessEqual => a.less_equals(b),
+    });
+    runtime.mem_stack.push(StackValue::from(result?));
+    Ok(())
+}
+
+pub fn contains(runtime: &mut Runtime, is_negated: bool) -> Result<(), RuntimeException> {
+    let container = runtime.mem_stack.pop().unwrap().into_ref();
+    let item = runtime.mem_stack.pop().unwrap().into_ref();
+    let is_member = match &*container.borrow() {
+        Value::Map(map) => Some(map.properties.contains_key(item.borrow().as_key()?)),
+        Value::List(list) => Some(list_contains(list.elements.clone(), &item)?),
+        Value::Tuple(tuple) => Some(list_contains(tuple.elements.clone(), &item)?),
+        Value::String(string) => match &*item.borrow() {
+            Value::String(substring) => Some(string.0.contains(substring.0.as_str())),
+            _ => {
+                return Err(exception::TYPE.runtime(format!(
+                    "Membership test on a string requires a string, got {:?}",
+                    item.borrow()
+                )));
+            }
+        },
+        Value::Instance(_) => None,
+        _ => {
+            return Err(exception::TYPE.runtime(format!(
+                "Membership test on a non-container type {:?}",
+                container.borrow()
+            )));
+        }
+    };
+    let is_member = match is_member {
+        Some(is_member) => is_member,
+        None => instance_contains(runtime, container, item)?,
     };
-    runtime.mem_stack.push(Rc::new(RefCell::new(result?)));
+    runtime
+        .mem_stack
+        .push(StackValue::Bool(is_member != is_negated));
     Ok(())
 }
+
+fn list_contains(elements: Vec<ValueRef>, item: &ValueRef) -> Result<bool, RuntimeException> {
+    // both are only borrowed, the item may be one of the elements or the container itself
+    let item_value = item.borrow();
+    for element in elements {
+        if element.borrow().equals_value(&item_value)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn instance_contains(
+    runtime: &mut Runtime,
+    instance: ValueRef,
+    item: ValueRef,
+) -> Result<bool, RuntimeException> {
+    // dispatch to the instance's `contains` method as if it was called from script
+    runtime.mem_stack.push(StackValue::from(instance));
+    runtime
+        .mem_stack
+        .push(StackValue::from(Value::string("contains".to_string())));
+    access_attr(runtime)?;
+    let contains_method = runtime.mem_stack.pop().unwrap().into_ref();
+    Ok(call_value(runtime, &contains_method, vec![item])?
+        .borrow()
+        .is_truthy())
+}
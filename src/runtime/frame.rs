@@ -1,24 +1,56 @@
 use crate::compiler::code_object::CodeObject;
-use crate::runtime::value::{Value, ValueRef};
+use crate::runtime::value::StackValue;
+use crate::runtime::value::exception::TraceEntry;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
+/// A script call frame: the variables of one execution of a code object, and where the
+/// dispatch loop is in it.
 #[derive(Debug)]
 pub struct RuntimeFrame {
     pub code_object_id: usize,
-    pub variables: Vec<ValueRef>,
+    pub(crate) variables: Rc<RefCell<Vec<StackValue>>>, // shared with references to the slots
     pub variable_index_lookup: HashMap<String, usize>,
+    pub(crate) code_object: Rc<CodeObject>,
+    pub(crate) ip: usize,
+    pub(crate) stack_base: usize, // memory stack length when the frame was entered
+    pub(crate) kind: FrameKind,
+}
+
+/// What a frame is executing, which decides what it hands back when it finishes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum FrameKind {
+    /// A function or method call, its result is left for the caller.
+    Call,
+    /// A class's `init`, the instance below the frame is the result instead.
+    Init,
+    /// A script, module or class body, kept around (cached) once finished.
+    Body,
 }
 
 impl RuntimeFrame {
-    pub fn from_co(code_object: &CodeObject) -> Self {
+    pub fn from_co(code_object: &Rc<CodeObject>) -> Self {
         Self {
             code_object_id: code_object.id,
-            variables: (0..code_object.variables.len())
-                .map(|_| Rc::new(RefCell::new(Value::Null)))
-                .collect(),
+            variables: Rc::new(RefCell::new(vec![
+                StackValue::Null;
+                code_object.variables.len()
+            ])),
             variable_index_lookup: code_object.variable_index_lookup.clone(),
+            code_object: code_object.clone(),
+            ip: 0,
+            stack_base: 0,
+            kind: FrameKind::Call,
+        }
+    }
+
+    /// The frame's place in the source; `ip` is past the operation it is executing.
+    pub(crate) fn trace_entry(&self) -> TraceEntry {
+        TraceEntry {
+            file: self.code_object.file.to_string(),
+            line: self.code_object.line_at(self.ip.saturating_sub(1)),
+            name: self.code_object.name.clone(),
         }
     }
 }
@@ -0,0 +1,213 @@
+use crate::runtime::value::{StackValue, Value, ValueRef};
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::rc::{Rc, Weak};
+
+/// Variable slots of a frame, shared with the references taken to them.
+pub(crate) type Slots = Rc<RefCell<Vec<StackValue>>>;
+
+const MIN_COLLECT_THRESHOLD: usize = 10_000;
+
+/// Trial-deletion cycle collector over the `Rc` value graph.
+///
+/// A cycle can only be closed by storing into a container that already exists, so the
+/// containers written to (and the frame slots references were taken to) are tracked as
+/// candidates. A collection walks the graph reachable from the candidates and subtracts the
+/// edges inside it from each node's strong count; nodes left with outside owners (stack,
+/// frames, constants, native code) keep everything they reach alive, the rest are cycles
+/// nothing else owns and get cleared, which lets `Rc` free them.
+pub(crate) struct CycleCollector {
+    candidates: HashMap<usize, Candidate>,
+    threshold: usize,
+    pub(crate) collections: usize,
+    pub(crate) collected: usize,
+}
+
+#[derive(Clone)]
+enum Candidate {
+    Value(Weak<RefCell<Value>>),
+    Slots(Weak<RefCell<Vec<StackValue>>>),
+}
+
+#[derive(Clone)]
+enum Node {
+    Value(ValueRef),
+    Slots(Slots),
+}
+
+impl Node {
+    fn key(&self) -> usize {
+        match self {
+            Node::Value(value) => Rc::as_ptr(value) as *const () as usize,
+            Node::Slots(slots) => Rc::as_ptr(slots) as *const () as usize,
+        }
+    }
+
+    fn strong_count(&self) -> usize {
+        match self {
+            Node::Value(value) => Rc::strong_count(value),
+            Node::Slots(slots) => Rc::strong_count(slots),
+        }
+    }
+
+    /// Outgoing edges, or `None` if the node is borrowed and cannot be inspected.
+    fn children(&self) -> Option<Vec<Node>> {
+        let mut children = Vec::new();
+        match self {
+            Node::Value(value) => value_children(&*value.try_borrow().ok()?, &mut children),
+            Node::Slots(slots) => children.extend(slots.try_borrow().ok()?.iter().filter_map(
+                |slot| match slot {
+                    StackValue::Object(value) => Some(Node::Value(value.clone())),
+                    _ => None,
+                },
+            )),
+        }
+        Some(children)
+    }
+
+    /// Drops the node's contents, breaking the cycles running through it.
+    fn clear(&self) {
+        match self {
+            Node::Value(value) => drop(value.replace(Value::Null)),
+            Node::Slots(slots) => drop(slots.take()),
+        }
+    }
+}
+
+fn value_children(value: &Value, children: &mut Vec<Node>) {
+    match value {
+        Value::List(list) => children.extend(list.elements.iter().cloned().map(Node::Value)),
+        Value::Tuple(tuple) => children.extend(tuple.elements.iter().cloned().map(Node::Value)),
+        Value::Map(map) => {
+            for (key, value) in map.properties.iter() {
+                // tuple keys hold element refs of their own
+                value_children(key, children);
+                children.push(Node::Value(value.clone()));
+            }
+        }
+        Value::Instance(instance) => {
+            children.push(Node::Value(instance.class.clone()));
+            children.extend(instance.attributes.values().cloned().map(Node::Value));
+        }
+        Value::Method(method) => children.extend(method.caller.clone().map(Node::Value)),
+        Value::RustMethod(method) => children.extend(method.caller.clone().map(Node::Value)),
+        Value::Class(class) => children.extend(class.parent.clone().map(Node::Value)),
+        Value::Reference(reference) => children.push(Node::Slots(reference.slots.clone())),
+        _ => {}
+    }
+}
+
+impl CycleCollector {
+    pub(crate) fn new() -> Self {
+        Self {
+            candidates: HashMap::new(),
+            threshold: MIN_COLLECT_THRESHOLD,
+            collections: 0,
+            collected: 0,
+        }
+    }
+
+    /// Records a container that was just stored into.
+    pub(crate) fn track_value(&mut self, value: &ValueRef) {
+        let key = Rc::as_ptr(value) as *const () as usize;
+        self.candidates
+            .insert(key, Candidate::Value(Rc::downgrade(value)));
+    }
+
+    /// Records frame slots a reference was taken to.
+    pub(crate) fn track_slots(&mut self, slots: &Slots) {
+        let key = Rc::as_ptr(slots) as *const () as usize;
+        self.candidates
+            .insert(key, Candidate::Slots(Rc::downgrade(slots)));
+    }
+
+    pub(crate) fn tracked(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub(crate) fn should_collect(&self) -> bool {
+        self.candidates.len() >= self.threshold
+    }
+
+    /// Frees unreachable cycles and returns how many values (and slot vectors) were cleared.
+    pub(crate) fn collect(&mut self) -> usize {
+        let roots: Vec<Node> = self
+            .candidates
+            .drain()
+            .filter_map(|(_, candidate)| match candidate {
+                Candidate::Value(value) => value.upgrade().map(Node::Value),
+                Candidate::Slots(slots) => slots.upgrade().map(Node::Slots),
+            })
+            .collect();
+
+        // every node reachable from the candidates, held once, with its edges inside the graph
+        let mut nodes: HashMap<usize, Node> = HashMap::new();
+        let mut edges: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut internal_refs: HashMap<usize, usize> = HashMap::new();
+        let mut opaque: HashSet<usize> = HashSet::new();
+        let mut pending: Vec<Node> = Vec::new();
+        let root_keys: HashSet<usize> = roots.iter().map(Node::key).collect();
+        for root in roots {
+            if let Entry::Vacant(entry) = nodes.entry(root.key()) {
+                entry.insert(root.clone());
+                pending.push(root);
+            }
+        }
+        while let Some(node) = pending.pop() {
+            let key = node.key();
+            drop(node);
+            let Some(children) = nodes[&key].children() else {
+                opaque.insert(key);
+                continue;
+            };
+            let mut child_keys = Vec::with_capacity(children.len());
+            for child in children {
+                let child_key = child.key();
+                *internal_refs.entry(child_key).or_default() += 1;
+                child_keys.push(child_key);
+                if let Entry::Vacant(entry) = nodes.entry(child_key) {
+                    entry.insert(child.clone());
+                    pending.push(child);
+                }
+            }
+            edges.insert(key, child_keys);
+        }
+
+        // nodes owned from outside the graph (beyond the collector's own handle) stay alive
+        let mut live: HashSet<usize> = HashSet::new();
+        let mut marking: Vec<usize> = nodes
+            .iter()
+            .filter(|(key, node)| {
+                opaque.contains(key)
+                    || node.strong_count() > 1 + internal_refs.get(key).copied().unwrap_or(0)
+            })
+            .map(|(key, _)| *key)
+            .collect();
+        while let Some(key) = marking.pop() {
+            if live.insert(key) {
+                marking.extend(edges.get(&key).into_iter().flatten().copied());
+            }
+        }
+
+        let garbage: Vec<Node> = nodes
+            .iter()
+            .filter(|(key, _)| !live.contains(key))
+            .map(|(_, node)| node.clone())
+            .collect();
+        garbage.iter().for_each(Node::clear);
+
+        // surviving candidates stay tracked, dropping their last outside owner orphans the cycle
+        for key in live.intersection(&root_keys) {
+            match nodes.remove(key) {
+                Some(Node::Value(value)) => self.track_value(&value),
+                Some(Node::Slots(slots)) => self.track_slots(&slots),
+                None => {}
+            }
+        }
+        self.collections += 1;
+        self.collected += garbage.len();
+        self.threshold = MIN_COLLECT_THRESHOLD.max(self.candidates.len() * 2);
+        garbage.len()
+    }
+}
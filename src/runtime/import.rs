@@ -0,0 +1,82 @@
+use crate::compiler::code_object::CodeObject;
+use crate::compiler::compiler::compile_source;
+use crate::runtime::Runtime;
+use crate::runtime::utils::{extract_string_ref, value_to_ref};
+use crate::runtime::value::exception;
+use crate::runtime::value::{RuntimeException, StackValue, Value, ValueRef};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+const MODULE_EXTENSIONS: [&str; 2] = ["pik", "txt"];
+
+pub(crate) fn import(
+    runtime: &mut Runtime,
+    code_object: &CodeObject,
+    path_index: usize,
+) -> Result<(), RuntimeException> {
+    let module_name = extract_string_ref(&code_object.constants[path_index]);
+    let module = load_module(runtime, &module_name)?;
+    runtime.mem_stack.push(StackValue::Object(module));
+    Ok(())
+}
+
+fn resolve_module_path(runtime: &Runtime, module_name: &str) -> Result<PathBuf, RuntimeException> {
+    for search_path in runtime.search_paths.iter() {
+        let candidate = search_path.join(module_name);
+        let found = if candidate.extension().is_some() && candidate.is_file() {
+            Some(candidate)
+        } else {
+            MODULE_EXTENSIONS
+                .iter()
+                .map(|extension| candidate.with_extension(extension))
+                .find(|path| path.is_file())
+        };
+        if let Some(path) = found {
+            return Ok(path.canonicalize().unwrap_or(path));
+        }
+    }
+    Err(exception::IMPORT.runtime(format!(
+        "Module {:?} not found in search path {:?}.",
+        module_name, runtime.search_paths
+    )))
+}
+
+/// Resolves, compiles and executes a module once; later imports of the same file share it.
+fn load_module(runtime: &mut Runtime, module_name: &str) -> Result<ValueRef, RuntimeException> {
+    let path = resolve_module_path(runtime, module_name)?;
+    if let Some(module) = runtime.modules.get(&path) {
+        return Ok(module.clone());
+    }
+    if let Some(cycle_start) = runtime.importing.iter().position(|p| p == &path) {
+        let cycle = runtime.importing[cycle_start..]
+            .iter()
+            .chain(std::iter::once(&path))
+            .map(|p| p.display().to_string())
+            .collect::<Vec<String>>()
+            .join(" -> ");
+        return Err(exception::IMPORT.runtime(format!("Circular import: {}", cycle)));
+    }
+    let source = std::fs::read_to_string(&path).map_err(|err| {
+        exception::IMPORT.runtime(format!("Cannot read module {}: {}", path.display(), err))
+    })?;
+    let module_code_object = Rc::new(compile_source(&source, &path, runtime.optimization));
+
+    runtime.importing.push(path.clone());
+    let result = runtime
+        .get_code_object_frame(&module_code_object)
+        .map(|_| ());
+    runtime.importing.pop();
+    result?;
+
+    let name = Path::new(module_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or(module_name.to_string());
+    let module = value_to_ref(Value::module(
+        name,
+        path.display().to_string(),
+        &module_code_object,
+    ));
+    runtime.modules.insert(path, module.clone());
+    Ok(module)
+}
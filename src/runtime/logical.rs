@@ -1,24 +1,18 @@
 use crate::runtime::Runtime;
-use crate::runtime::value::{RuntimeException, Value};
-use std::cell::RefCell;
-use std::rc::Rc;
+use crate::runtime::value::{RuntimeException, StackValue};
 
 pub fn logical_and(runtime: &mut Runtime) -> Result<(), RuntimeException> {
     let b = runtime.mem_stack.pop().unwrap();
     let a = runtime.mem_stack.pop().unwrap();
-    let result = a.borrow().is_truthy() && b.borrow().is_truthy();
-    runtime
-        .mem_stack
-        .push(Rc::new(RefCell::new(Value::bool(result))));
+    let result = a.is_truthy() && b.is_truthy();
+    runtime.mem_stack.push(StackValue::Bool(result));
     Ok(())
 }
 
 pub fn logical_or(runtime: &mut Runtime) -> Result<(), RuntimeException> {
     let b = runtime.mem_stack.pop().unwrap();
     let a = runtime.mem_stack.pop().unwrap();
-    let result = a.borrow().is_truthy() || b.borrow().is_truthy();
-    runtime
-        .mem_stack
-        .push(Rc::new(RefCell::new(Value::bool(result))));
+    let result = a.is_truthy() || b.is_truthy();
+    runtime.mem_stack.push(StackValue::Bool(result));
     Ok(())
 }
@@ -1,7 +1,10 @@
 use crate::runtime::Runtime;
 use crate::runtime::call::{expect_args_count, get_function_runtime_frame};
-use crate::runtime::utils::{extract_class_ref, extract_function_ref};
-use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use crate::runtime::frame::{FrameKind, RuntimeFrame};
+use crate::runtime::utils::extract_class_ref;
+use crate::runtime::value::exception;
+use crate::runtime::value::{RuntimeException, StackValue, Value, ValueRef};
+use indexmap::IndexMap;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -13,20 +16,21 @@ pub(crate) fn make_map(
     let properties_kv: Vec<ValueRef> = runtime
         .mem_stack
         .drain(runtime.mem_stack.len() - property_count..)
+        .map(StackValue::into_ref)
         .collect();
 
-    let mut properties = HashMap::new();
+    let mut properties = IndexMap::new();
     for kv in properties_kv.chunks(2) {
         match kv {
             [k, v] => {
-                properties.insert(k.borrow().clone(), v.clone());
+                properties.insert(k.borrow().to_key()?, v.clone());
             }
             _ => unreachable!("Map key without a value"),
         }
     }
     runtime
         .mem_stack
-        .push(Rc::new(RefCell::new(Value::map(properties))));
+        .push(StackValue::from(Value::map(properties)));
     Ok(())
 }
 
@@ -34,11 +38,117 @@ pub(crate) fn make_list(runtime: &mut Runtime, list_size: usize) -> Result<(), R
     let list_items = runtime
         .mem_stack
         .drain(runtime.mem_stack.len() - list_size..)
-        .rev()
+        .map(StackValue::into_ref)
         .collect();
     runtime
         .mem_stack
-        .push(Rc::new(RefCell::new(Value::list(list_items))));
+        .push(StackValue::from(Value::list(list_items)));
+    Ok(())
+}
+
+pub(crate) fn make_tuple(runtime: &mut Runtime, tuple_size: usize) -> Result<(), RuntimeException> {
+    let tuple_items = runtime
+        .mem_stack
+        .drain(runtime.mem_stack.len() - tuple_size..)
+        .map(StackValue::into_ref)
+        .collect();
+    runtime
+        .mem_stack
+        .push(StackValue::from(Value::tuple(tuple_items)));
+    Ok(())
+}
+
+/// Replaces a sequence on top of the stack with its `count` elements, the first one on top.
+pub(crate) fn unpack_sequence(runtime: &mut Runtime, count: usize) -> Result<(), RuntimeException> {
+    let sequence = runtime.mem_stack.pop().unwrap().into_ref();
+    let elements = sequence.borrow().iter_elements()?;
+    if elements.len() != count {
+        return Err(exception::VALUE.runtime(format!(
+            "Expected {} values to unpack, got {}",
+            count,
+            elements.len()
+        )));
+    }
+    runtime
+        .mem_stack
+        .extend(elements.into_iter().rev().map(StackValue::from));
+    Ok(())
+}
+
+/// Appends the value on top of the stack to the list below it.
+pub(crate) fn list_append(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let element = runtime.mem_stack.pop().unwrap().into_ref();
+    let list = stack_top_object(runtime)?;
+    if let Value::List(list) = &mut *list.borrow_mut() {
+        list.elements.push(element);
+    }
+    Ok(())
+}
+
+/// Extends the list below the top of the stack with the elements of the iterable on top.
+pub(crate) fn list_extend(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let iterable = runtime.mem_stack.pop().unwrap().into_ref();
+    let elements = iterable.borrow().iter_elements()?;
+    let list = stack_top_object(runtime)?;
+    if let Value::List(list) = &mut *list.borrow_mut() {
+        list.elements.extend(elements);
+    }
+    Ok(())
+}
+
+/// Inserts the key and value on top of the stack into the map below them.
+pub(crate) fn map_insert(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let value = runtime.mem_stack.pop().unwrap().into_ref();
+    let key = runtime.mem_stack.pop().unwrap().into_ref();
+    let map = stack_top_object(runtime)?;
+    if let Value::Map(map) = &mut *map.borrow_mut() {
+        map.properties.insert(key.borrow().to_key()?, value);
+    }
+    Ok(())
+}
+
+/// Merges the map on top of the stack into the map below it, later keys winning.
+pub(crate) fn map_update(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let other = runtime.mem_stack.pop().unwrap().into_ref();
+    let properties = match &*other.borrow() {
+        Value::Map(other) => other.properties.clone(),
+        value => {
+            return Err(exception::TYPE.runtime(format!(
+                "Only maps can be spread into a map, got '{}'",
+                value.type_name()
+            )));
+        }
+    };
+    let map = stack_top_object(runtime)?;
+    if let Value::Map(map) = &mut *map.borrow_mut() {
+        map.properties.extend(properties);
+    }
+    Ok(())
+}
+
+/// The list or map being built below the top of the stack.
+fn stack_top_object(runtime: &Runtime) -> Result<&ValueRef, RuntimeException> {
+    match runtime.mem_stack.last().unwrap() {
+        StackValue::Object(object) => Ok(object),
+        _ => Err(exception::TYPE.runtime("Expected a list or map under construction".to_string())),
+    }
+}
+
+fn slice_bound(bound: StackValue) -> Result<Option<i64>, RuntimeException> {
+    match bound {
+        StackValue::Int(int) => Ok(Some(int)),
+        StackValue::Null => Ok(None),
+        _ => Err(exception::TYPE.runtime("Slice indices must be integers or null".to_string())),
+    }
+}
+
+pub(crate) fn make_slice(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let step = slice_bound(runtime.mem_stack.pop().unwrap())?;
+    let end = slice_bound(runtime.mem_stack.pop().unwrap())?;
+    let start = slice_bound(runtime.mem_stack.pop().unwrap())?;
+    runtime
+        .mem_stack
+        .push(StackValue::from(Value::slice(start, end, step)));
     Ok(())
 }
 
@@ -46,23 +156,32 @@ pub(crate) fn make_class(
     runtime: &mut Runtime,
     is_inheriting: bool,
 ) -> Result<(), RuntimeException> {
-    let uncasted_class = runtime.mem_stack.pop().unwrap();
-    let superclass_ref = is_inheriting.then(|| runtime.mem_stack.pop().unwrap().clone());
+    let uncasted_class = runtime.mem_stack.pop().unwrap().into_ref();
+    let superclass_ref = is_inheriting.then(|| runtime.mem_stack.pop().unwrap().into_ref());
     let class_code_obj = match &*uncasted_class.borrow() {
         Value::Class(class_value) => class_value.body.clone(),
-        _ => unreachable!("Make class called on non-class value, compiler level error"),
+        _ => {
+            return Err(
+                exception::TYPE.runtime("Cannot make a class of a non-class value".to_string())
+            );
+        }
     };
-    runtime.mem_stack.push(Rc::new(RefCell::new(Value::class(
+    if let Some(superclass) = &superclass_ref
+        && !matches!(&*superclass.borrow(), Value::Class(_))
+    {
+        return Err(exception::TYPE.runtime("Classes can only inherit from classes".to_string()));
+    }
+    runtime.mem_stack.push(StackValue::from(Value::class(
         superclass_ref,
         class_code_obj,
-    ))));
+    )));
     Ok(())
 }
 
 pub(crate) fn make_instance(
     runtime: &mut Runtime,
     value_cls: ValueRef,
-    mut args: Vec<ValueRef>,
+    mut args: Vec<StackValue>,
 ) -> Result<(), RuntimeException> {
     let class_value = extract_class_ref(&value_cls);
     let class_code_object = class_value.body;
@@ -70,17 +189,33 @@ pub(crate) fn make_instance(
     let frame = runtime.get_code_object_frame(&class_code_object)?;
     // execute init if exists
     if let Some(init_func_index) = class_code_object.variable_index_lookup.get("init") {
-        if let Some(init_func) = frame.variables.get(*init_func_index) {
-            let init_func_value = extract_function_ref(init_func);
-            args.push(instance.clone());
+        let init_func = frame
+            .variables
+            .borrow()
+            .get(*init_func_index)
+            .map(StackValue::to_ref);
+        if let Some(init_func) = init_func {
+            let init_func_value = match &*init_func.borrow() {
+                Value::Function(function_value) => function_value.clone(),
+                Value::Method(method_value) => method_value.function.clone(),
+                _ => {
+                    return Err(
+                        exception::TYPE.runtime("A class init must be a method".to_string())
+                    );
+                }
+            };
+            args.push(StackValue::Object(instance.clone()));
             expect_args_count(args.len(), init_func_value.parameters.len())?;
-            runtime.push_to_frame_stack(get_function_runtime_frame(&init_func_value, args));
-            runtime.execute(&init_func_value.body)?;
-            runtime.pop_from_frame_stack();
+            // the call results in the instance, whatever init returns
+            runtime.mem_stack.push(StackValue::Object(instance));
+            return runtime.push_to_frame_stack(RuntimeFrame {
+                kind: FrameKind::Init,
+                ..get_function_runtime_frame(&init_func_value, args)
+            });
         } else {
             panic!("Invalid class init function index, compiler level error");
         }
     };
-    runtime.mem_stack.push(instance);
+    runtime.mem_stack.push(StackValue::Object(instance));
     Ok(())
 }
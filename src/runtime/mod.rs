@@ -1,11 +1,16 @@
 mod access;
 mod assign;
+pub(crate) mod builtins;
 mod call;
 mod compare;
 mod frame;
+mod gc;
+mod import;
 mod logical;
 mod make;
+mod pattern;
 pub mod runtime;
+mod traceback;
 mod utils;
 pub mod value;
 mod vm;
@@ -0,0 +1,91 @@
+use crate::runtime::Runtime;
+use crate::runtime::value::exception;
+use crate::runtime::value::get_class_attr;
+use crate::runtime::value::{RuntimeException, StackValue, Value, ValueRef};
+
+fn push_bool(runtime: &mut Runtime, value: bool) {
+    runtime.mem_stack.push(StackValue::Bool(value));
+}
+
+pub(crate) fn match_sequence(runtime: &mut Runtime, length: usize) -> Result<(), RuntimeException> {
+    let subject = runtime.mem_stack.pop().unwrap().into_ref();
+    let is_match = match &*subject.borrow() {
+        Value::List(list) => list.elements.len() == length,
+        Value::Tuple(tuple) => tuple.elements.len() == length,
+        _ => false,
+    };
+    push_bool(runtime, is_match);
+    Ok(())
+}
+
+pub(crate) fn match_mapping(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let subject = runtime.mem_stack.pop().unwrap().into_ref();
+    let is_match = matches!(&*subject.borrow(), Value::Map(_));
+    push_bool(runtime, is_match);
+    Ok(())
+}
+
+fn is_subclass(class: &ValueRef, class_id: usize) -> bool {
+    match &*class.borrow() {
+        Value::Class(class_value) => {
+            class_value.id == class_id
+                || class_value
+                    .parent
+                    .as_ref()
+                    .is_some_and(|parent| is_subclass(parent, class_id))
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn match_class(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let class = runtime.mem_stack.pop().unwrap().into_ref();
+    let subject = runtime.mem_stack.pop().unwrap().into_ref();
+    let class_id = match &*class.borrow() {
+        Value::Class(class_value) => class_value.id,
+        _ => {
+            return Err(exception::TYPE.runtime("Class patterns require a class".to_string()));
+        }
+    };
+    let is_match = match &*subject.borrow() {
+        Value::Instance(instance) => is_subclass(&instance.class, class_id),
+        _ => false,
+    };
+    push_bool(runtime, is_match);
+    Ok(())
+}
+
+pub(crate) fn load_match_arg(runtime: &mut Runtime, index: usize) -> Result<(), RuntimeException> {
+    let class = runtime.mem_stack.pop().unwrap().into_ref();
+    let class_value = match &*class.borrow() {
+        Value::Class(class_value) => class_value.clone(),
+        _ => {
+            return Err(exception::TYPE.runtime("Class patterns require a class".to_string()));
+        }
+    };
+    // positional sub-patterns follow the class init parameters, self excluded
+    let parameters = match get_class_attr(runtime, &class_value, &"init".to_string())? {
+        Some(init) => match &*init.borrow() {
+            Value::Method(method_value) => method_value
+                .function
+                .parameters
+                .get(1..)
+                .unwrap_or_default()
+                .to_vec(),
+            Value::Function(function_value) => function_value.parameters.clone(),
+            _ => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+    let name = parameters
+        .get(index)
+        .ok_or(exception::TYPE.runtime(format!(
+            "Class pattern accepts {} positional sub-patterns, got at least {}",
+            parameters.len(),
+            index + 1
+        )))?;
+    runtime
+        .mem_stack
+        .push(StackValue::from(Value::string(name.clone())));
+    Ok(())
+}
@@ -1,25 +1,47 @@
 use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::OpIndex;
 use crate::compiler::code_object::CodeObject;
+use crate::compiler::compiler::OptimizationLevel;
 use crate::runtime::access::*;
 use crate::runtime::assign::*;
+use crate::runtime::builtins::BUILTINS;
 use crate::runtime::call::*;
 use crate::runtime::compare::*;
-use crate::runtime::frame::RuntimeFrame;
+use crate::runtime::frame::{FrameKind, RuntimeFrame};
+use crate::runtime::gc::CycleCollector;
+use crate::runtime::import::*;
 use crate::runtime::logical::*;
 use crate::runtime::make::*;
+use crate::runtime::pattern::*;
+use crate::runtime::traceback::format_traceback;
+use crate::runtime::value::exception;
 use crate::runtime::value::traits::Binary;
-use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use crate::runtime::value::{RuntimeException, StackValue, Value, ValueRef};
 use crate::runtime::vm::*;
 use std;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 
+const DEFAULT_RECURSION_LIMIT: usize = 10_000;
+// each nested dispatch loop takes native stack, so re-entering through native code stops well
+// before the frame limit would
+const NESTED_EXECUTION_LIMIT: usize = 500;
+
 pub struct Runtime {
-    pub(crate) mem_stack: Vec<ValueRef>,
+    pub(crate) mem_stack: Vec<StackValue>,
     pub(crate) frames_stack: Vec<RuntimeFrame>,
     pub(crate) frames_cache: HashMap<usize, RuntimeFrame>,
     pub(crate) frames_stack_id_lookup: HashMap<usize, Vec<usize>>,
+    pub(crate) search_paths: Vec<PathBuf>,
+    pub(crate) modules: HashMap<PathBuf, ValueRef>, // resolved module path -> module value
+    pub(crate) importing: Vec<PathBuf>,             // modules currently being executed
+    pub(crate) builtins: Vec<ValueRef>,
+    pub(crate) gc: CycleCollector,
+    pub(crate) recursion_limit: usize, // script frames active at once
+    pub(crate) nested_executions: usize, // dispatch loops running, one per native re-entry
+    pub(crate) optimization: OptimizationLevel, // imported modules compile at the entry's level
 }
 
 impl Runtime {
@@ -29,10 +51,56 @@ impl Runtime {
             frames_stack: Vec::new(),
             frames_cache: HashMap::new(),
             frames_stack_id_lookup: HashMap::new(),
+            search_paths: Vec::new(),
+            modules: HashMap::new(),
+            importing: Vec::new(),
+            builtins: BUILTINS
+                .iter()
+                .map(|(_, function)| Rc::new(RefCell::new(Value::rust_method(*function, None))))
+                .collect(),
+            gc: CycleCollector::new(),
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            nested_executions: 0,
+            optimization: OptimizationLevel::default(),
         }
     }
 
-    pub(crate) fn push_to_frame_stack(&mut self, frame: RuntimeFrame) {
+    /// Calls a script function, method, class or native method and returns its result.
+    pub fn call_value(
+        &mut self,
+        callee: &ValueRef,
+        args: Vec<ValueRef>,
+    ) -> Result<ValueRef, RuntimeException> {
+        call_value(self, callee, args)
+    }
+
+    /// Adds a directory searched (in insertion order) when resolving imports.
+    pub fn add_search_path(&mut self, path: PathBuf) {
+        self.search_paths.push(path);
+    }
+
+    /// Sets how many script frames may be active at once; entering one more raises a
+    /// `RecursionError`.
+    pub fn set_recursion_limit(&mut self, limit: usize) {
+        self.recursion_limit = limit;
+    }
+
+    /// Sets the level imported modules are compiled at.
+    pub fn set_optimization_level(&mut self, optimization: OptimizationLevel) {
+        self.optimization = optimization;
+    }
+
+    pub(crate) fn push_to_frame_stack(
+        &mut self,
+        mut frame: RuntimeFrame,
+    ) -> Result<(), RuntimeException> {
+        if self.frames_stack.len() >= self.recursion_limit {
+            return Err(exception::RECURSION.runtime(format!(
+                "Maximum recursion depth of {} exceeded",
+                self.recursion_limit
+            )));
+        }
+        frame.stack_base = self.mem_stack.len();
         if self
             .frames_stack
             .last()
@@ -45,6 +113,7 @@ impl Runtime {
                 .push(self.frames_stack.len());
         }
         self.frames_stack.push(frame);
+        Ok(())
     }
 
     pub(crate) fn pop_from_frame_stack(&mut self) -> RuntimeFrame {
@@ -65,12 +134,6 @@ impl Runtime {
         popped_frame
     }
 
-    pub(crate) fn pop_mem_stack_value_or_null(&mut self) -> ValueRef {
-        self.mem_stack
-            .pop()
-            .unwrap_or_else(|| Rc::new(RefCell::new(Value::Null)))
-    }
-    
     pub(crate) fn pop_mem_stack(&mut self) -> Result<(), RuntimeException> {
         self.mem_stack.pop();
         Ok(())
@@ -78,26 +141,73 @@ impl Runtime {
 
     pub(crate) fn get_code_object_frame(
         &mut self,
-        code_object: &CodeObject,
+        code_object: &Rc<CodeObject>,
     ) -> Result<&RuntimeFrame, RuntimeException> {
         if self.frames_cache.contains_key(&code_object.id) {
             return Ok(self.frames_cache.get(&code_object.id).unwrap());
         }
-        self.push_to_frame_stack(RuntimeFrame::from_co(code_object));
-        self.execute(&code_object)?;
-        let frame = self.pop_from_frame_stack();
+        self.push_to_frame_stack(RuntimeFrame {
+            kind: FrameKind::Body,
+            ..RuntimeFrame::from_co(code_object)
+        })?;
+        let frame = self.execute()?;
         self.frames_cache.insert(code_object.id, frame);
         Ok(self.frames_cache.get(&code_object.id).unwrap())
     }
 
-    pub(crate) fn execute(&mut self, code_object: &CodeObject) -> Result<(), RuntimeException> {
-        let mut ip = 0;
-        while let Some(byte_op) = code_object.operations.get(ip) {
+    /// Runs the frame on top of the frame stack until it finishes and returns it. Script calls
+    /// push their frames onto this same loop, only native code calling back into script and
+    /// class or module bodies executed on first use start a nested one.
+    pub(crate) fn execute(&mut self) -> Result<RuntimeFrame, RuntimeException> {
+        let entry_depth = self.frames_stack.len();
+        let result = if self.nested_executions >= NESTED_EXECUTION_LIMIT {
+            Err(exception::RECURSION.runtime(format!(
+                "Maximum depth of {} nested calls from native code exceeded",
+                NESTED_EXECUTION_LIMIT
+            )))
+        } else {
+            self.nested_executions += 1;
+            let result = self.dispatch(entry_depth);
+            self.nested_executions -= 1;
+            result
+        };
+        result.map_err(|mut err| {
+            err.traceback.extend(
+                self.frames_stack[entry_depth - 1..]
+                    .iter()
+                    .rev()
+                    .map(RuntimeFrame::trace_entry),
+            );
+            // unwind the frames entered since, so the runtime stays usable after the error
+            let stack_base = self.frames_stack[entry_depth - 1].stack_base;
+            while self.frames_stack.len() >= entry_depth {
+                self.pop_from_frame_stack();
+            }
+            self.mem_stack.truncate(stack_base);
+            err
+        })
+    }
+
+    fn dispatch(&mut self, entry_depth: usize) -> Result<RuntimeFrame, RuntimeException> {
+        let (mut code_object, mut ip) = self.resume_point();
+        loop {
+            // running past the last operation returns, as bodies without a `return` do
+            let byte_op = if ip < code_object.code.len() {
+                OpIndex::decode(&code_object.code, &mut ip).expect("malformed operation")
+            } else {
+                OpIndex::without_op(ByteOp::ReturnValue)
+            };
             let operation_result = match byte_op.operation {
-                ByteOp::LoadConstant => load_constant(self, code_object, byte_op.operand),
+                ByteOp::LoadConstant => load_constant(self, &code_object, byte_op.operand),
                 ByteOp::LoadLocal => load_local(self, byte_op.operand),
                 ByteOp::LoadScope => load_scope(self, byte_op.operand),
                 ByteOp::LoadNonlocal => load_nonlocal(self, byte_op.operand),
+                ByteOp::LoadNull => load_null(self),
+                ByteOp::LoadBuiltin => load_builtin(self, byte_op.operand),
+                ByteOp::LoadReference => load_reference(self, &code_object, byte_op.operand),
+                ByteOp::LoadNonlocalReference => load_nonlocal_reference(self, byte_op.operand),
+                ByteOp::Deref => deref(self),
+                ByteOp::StoreDeref => store_deref(self),
                 ByteOp::BinarySubscribe => binary_subscribe(self),
                 ByteOp::AccessAttribute => access_attr(self),
                 ByteOp::PreAssign => pre_assign(self, byte_op.operand),
@@ -106,15 +216,38 @@ impl Runtime {
                 ByteOp::MakeMap => make_map(self, byte_op.operand),
                 ByteOp::MakeList => make_list(self, byte_op.operand),
                 ByteOp::MakeClass => make_class(self, byte_op.operand == 1),
-                ByteOp::Call => call(self, byte_op.operand),
-                ByteOp::Add => apply_bin_op(self, Value::add),
-                ByteOp::Sub => apply_bin_op(self, Value::sub),
-                ByteOp::Mul => apply_bin_op(self, Value::mul),
-                ByteOp::Div => apply_bin_op(self, Value::div),
-                ByteOp::IntDiv => apply_bin_op(self, Value::int_div),
-                ByteOp::Mod => apply_bin_op(self, Value::modulus),
-                ByteOp::Exp => apply_bin_op(self, Value::pow),
+                ByteOp::MakeSlice => make_slice(self),
+                ByteOp::MakeTuple => make_tuple(self, byte_op.operand),
+                ByteOp::UnpackSequence => unpack_sequence(self, byte_op.operand),
+                ByteOp::ListAppend => list_append(self),
+                ByteOp::ListExtend => list_extend(self),
+                ByteOp::MapInsert => map_insert(self),
+                ByteOp::MapUpdate => map_update(self),
+                ByteOp::Call | ByteOp::CallSpread | ByteOp::TailCall => {
+                    // the callee's frame, if any, becomes the one executing
+                    self.frames_stack.last_mut().unwrap().ip = ip;
+                    match byte_op.operation {
+                        ByteOp::Call => call(self, byte_op.operand)?,
+                        ByteOp::TailCall => tail_call(self, byte_op.operand)?,
+                        _ => call_spread(self)?,
+                    }
+                    (code_object, ip) = self.resume_point();
+                    continue;
+                }
+                ByteOp::Add => apply_bin_op(self, ByteOp::Add, Value::add),
+                ByteOp::Sub => apply_bin_op(self, ByteOp::Sub, Value::sub),
+                ByteOp::Mul => apply_bin_op(self, ByteOp::Mul, Value::mul),
+                ByteOp::Div => apply_bin_op(self, ByteOp::Div, Value::div),
+                ByteOp::IntDiv => apply_bin_op(self, ByteOp::IntDiv, Value::int_div),
+                ByteOp::Mod => apply_bin_op(self, ByteOp::Mod, Value::modulus),
+                ByteOp::Exp => apply_bin_op(self, ByteOp::Exp, Value::pow),
                 ByteOp::Compare => compare(self, byte_op.operand),
+                ByteOp::Contains => contains(self, byte_op.operand == 1),
+                ByteOp::MatchSequence => match_sequence(self, byte_op.operand),
+                ByteOp::MatchMapping => match_mapping(self),
+                ByteOp::MatchClass => match_class(self),
+                ByteOp::LoadMatchArg => load_match_arg(self, byte_op.operand),
+                ByteOp::Import => import(self, &code_object, byte_op.operand),
                 ByteOp::LogicalAnd => logical_and(self),
                 ByteOp::LogicalOr => logical_or(self),
                 ByteOp::Pop => self.pop_mem_stack(),
@@ -129,33 +262,71 @@ impl Runtime {
                     ip = byte_op.operand;
                     continue;
                 }
-                ByteOp::ReturnValue => return Ok(()),
+                ByteOp::ReturnValue => {
+                    let frame = self.finish_frame();
+                    if self.frames_stack.len() < entry_depth {
+                        return Ok(frame);
+                    }
+                    (code_object, ip) = self.resume_point();
+                    continue;
+                }
                 _ => panic!("Unimplemented {:?}", byte_op.operation),
             };
-            ip += 1;
-            match operation_result {
-                Err(err) => return Err(err),
-                _ => {}
+            if self.gc.should_collect() {
+                self.gc.collect();
+            }
+            if let Err(err) = operation_result {
+                // the traceback reports the operation that failed
+                self.frames_stack.last_mut().unwrap().ip = ip;
+                return Err(err);
             }
         }
-        Ok(())
     }
 
-    pub fn run(&mut self, code_object: &CodeObject) {
-        // self.print_ast(code_object);
-        let frame = RuntimeFrame::from_co(code_object);
-        self.push_to_frame_stack(frame);
-        let status = self.execute(code_object); // todo catch this
-        if let Err(err) = status {
-            println!("{:?}", err);
+    fn resume_point(&self) -> (Rc<CodeObject>, usize) {
+        let frame = self.frames_stack.last().unwrap();
+        (frame.code_object.clone(), frame.ip)
+    }
+
+    /// Pops the finished frame and hands its result (the value on top of its part of the
+    /// memory stack, or null) to the caller.
+    fn finish_frame(&mut self) -> RuntimeFrame {
+        let frame = self.pop_from_frame_stack();
+        let return_value = if self.mem_stack.len() > frame.stack_base {
+            self.mem_stack.pop().unwrap()
         } else {
-            let result = self.pop_from_frame_stack();
-            self.print_current_stack_status(code_object, result);
+            StackValue::Null
+        };
+        self.mem_stack.truncate(frame.stack_base);
+        if frame.kind == FrameKind::Call {
+            self.mem_stack.push(return_value);
+        }
+        frame
+    }
+
+    /// Runs a script, returning whether it finished; the traceback of an uncaught exception
+    /// goes to stderr.
+    pub fn run(&mut self, code_object: CodeObject) -> bool {
+        // self.print_ast(code_object);
+        let code_object = Rc::new(code_object);
+        let frame = RuntimeFrame {
+            kind: FrameKind::Body,
+            ..RuntimeFrame::from_co(&code_object)
+        };
+        match self.push_to_frame_stack(frame).and_then(|_| self.execute()) {
+            Err(err) => {
+                eprint!("{}", format_traceback(&err));
+                false
+            }
+            Ok(frame) => {
+                self.print_current_stack_status(&code_object, frame);
+                true
+            }
         }
     }
 
     pub fn print_ast(&self, co: &CodeObject) {
-        for q in co.operations.iter() {
+        for (_, q) in co.instructions() {
             println!("{:?}", q);
         }
     }
@@ -168,22 +339,22 @@ impl Runtime {
         println!("stack:");
         self.mem_stack
             .iter()
-            .for_each(|item| println!("mem {:?}", item.borrow().clone()));
+            .for_each(|item| item.with_value(|value| println!("mem {:?}", value)));
         println!("variables:");
         runtime_frame
             .variables
+            .borrow()
             .iter()
-            .for_each(|item| println!("var {:?}", item.borrow().clone()));
+            .for_each(|item| item.with_value(|value| println!("var {:?}", value)));
         println!("bytecode:");
-        for (i, val) in code_object.operations.iter().enumerate() {
-            println!("{}: {:?}", i, val);
+        for (offset, val) in code_object.instructions() {
+            println!("{}: {:?}", offset, val);
         }
         println!(
             "hex: {:?}",
             code_object
-                .operations
-                .iter()
-                .map(|item| item.hex())
+                .instructions()
+                .map(|(_, item)| item.hex())
                 .collect::<Vec<String>>()
                 .join(" ")
         );
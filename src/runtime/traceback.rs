@@ -0,0 +1,60 @@
+use crate::runtime::value::RuntimeException;
+use crate::runtime::value::exception::TraceEntry;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+// identical entries in a row past this many are summarized, as deep recursion repeats them
+const REPEATED_ENTRIES_SHOWN: usize = 3;
+
+/// Formats an exception that escaped the script, most recent call last, quoting each line from
+/// its source file when the file can still be read.
+pub(crate) fn format_traceback(exception: &RuntimeException) -> String {
+    let mut sources: HashMap<&str, Option<Vec<String>>> = HashMap::new();
+    let mut output = String::from("Traceback (most recent call last):\n");
+    let mut entries = exception.traceback.iter().rev().peekable();
+    while let Some(entry) = entries.next() {
+        let mut repeats = 0;
+        while entries.peek() == Some(&entry) {
+            entries.next();
+            repeats += 1;
+        }
+        for _ in 0..=repeats.min(REPEATED_ENTRIES_SHOWN - 1) {
+            write_entry(&mut output, entry, &mut sources);
+        }
+        if repeats >= REPEATED_ENTRIES_SHOWN {
+            let more = repeats + 1 - REPEATED_ENTRIES_SHOWN;
+            writeln!(output, "  [Previous line repeated {} more times]", more).unwrap();
+        }
+    }
+    writeln!(output, "{}: {}", exception.kind.0, exception.message).unwrap();
+    output
+}
+
+fn write_entry<'a>(
+    output: &mut String,
+    entry: &'a TraceEntry,
+    sources: &mut HashMap<&'a str, Option<Vec<String>>>,
+) {
+    let Some(line) = entry.line else {
+        writeln!(output, "  File \"{}\", in {}", entry.file, entry.name).unwrap();
+        return;
+    };
+    writeln!(
+        output,
+        "  File \"{}\", line {}, in {}",
+        entry.file, line, entry.name
+    )
+    .unwrap();
+    let source_lines = sources.entry(&entry.file).or_insert_with(|| {
+        std::fs::read_to_string(&entry.file)
+            .ok()
+            .map(|source| source.lines().map(String::from).collect())
+    });
+    let source_line = source_lines
+        .as_ref()
+        .zip(line.checked_sub(1))
+        .and_then(|(source_lines, index)| source_lines.get(index));
+    if let Some(source_line) = source_line {
+        writeln!(output, "    {}", source_line.trim()).unwrap();
+    }
+}
@@ -13,13 +13,6 @@ pub fn extract_class_ref(value: &ValueRef) -> ClassValue {
     }
 }
 
-pub fn extract_function_ref(value: &ValueRef) -> FunctionValue {
-    match &*value.borrow() {
-        Value::Function(value) => value.clone(),
-        _ => panic!("Unexpected value of type {:?}", *value.borrow()),
-    }
-}
-
 pub fn extract_string_ref(value: &ValueRef) -> String {
     match &*value.borrow() {
         Value::String(value) => value.clone().0,
@@ -8,8 +8,10 @@ use crate::runtime::value::types::string::StringValue;
 use crate::runtime::value::types::exception::ExceptionValue;
 use crate::runtime::value::*;
 use crate::utils::counter::Counter;
+use std::cell::RefCell;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::rc::Rc;
 
 pub(crate) static RUNTIME_VALUE_ID: Counter = Counter::new();
 
@@ -21,12 +23,16 @@ pub enum Value {
     Bool(BoolValue),
     Map(MapValue),
     List(ListValue),
+    Tuple(TupleValue),
     Function(FunctionValue),
     Method(MethodValue),
     RustMethod(RustMethodValue),
     Class(ClassValue),
     Instance(InstanceValue),
     Exception(ExceptionValue),
+    Slice(SliceValue),
+    Module(ModuleValue),
+    Reference(ReferenceValue),
     Null,
 }
 
@@ -37,7 +43,7 @@ impl Binary for Value {
             Value::Float(value) => value.add(other),
             Value::String(value) => value.add(other),
             Value::Bool(value) => value.add(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "+", other),
         }
     }
 
@@ -47,7 +53,7 @@ impl Binary for Value {
             Value::Float(value) => value.sub(other),
             Value::String(value) => value.sub(other),
             Value::Bool(value) => value.sub(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "-", other),
         }
     }
 
@@ -57,7 +63,7 @@ impl Binary for Value {
             Value::Float(value) => value.mul(other),
             Value::String(value) => value.mul(other),
             Value::Bool(value) => value.mul(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "*", other),
         }
     }
 
@@ -67,7 +73,7 @@ impl Binary for Value {
             Value::Float(value) => value.div(other),
             Value::String(value) => value.div(other),
             Value::Bool(value) => value.div(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "/", other),
         }
     }
 
@@ -77,7 +83,7 @@ impl Binary for Value {
             Value::Float(value) => value.int_div(other),
             Value::String(value) => value.int_div(other),
             Value::Bool(value) => value.int_div(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "//", other),
         }
     }
 
@@ -87,7 +93,7 @@ impl Binary for Value {
             Value::Float(value) => value.modulus(other),
             Value::String(value) => value.modulus(other),
             Value::Bool(value) => value.modulus(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "%", other),
         }
     }
 
@@ -97,7 +103,7 @@ impl Binary for Value {
             Value::Float(value) => value.pow(other),
             Value::String(value) => value.pow(other),
             Value::Bool(value) => value.pow(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "**", other),
         }
     }
 
@@ -107,7 +113,7 @@ impl Binary for Value {
             Value::Float(value) => value.equals(other),
             Value::String(value) => value.equals(other),
             Value::Bool(value) => value.equals(other),
-            _ => unimplemented!(),
+            _ => Ok(Value::bool(structurally_equal(self, other, &mut Vec::new()))),
         }
     }
 
@@ -117,7 +123,7 @@ impl Binary for Value {
             Value::Float(value) => value.greater(other),
             Value::String(value) => value.greater(other),
             Value::Bool(value) => value.greater(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, ">", other),
         }
     }
 
@@ -127,7 +133,7 @@ impl Binary for Value {
             Value::Float(value) => value.greater_equals(other),
             Value::String(value) => value.greater_equals(other),
             Value::Bool(value) => value.greater_equals(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, ">=", other),
         }
     }
 
@@ -137,7 +143,7 @@ impl Binary for Value {
             Value::Float(value) => value.less(other),
             Value::String(value) => value.less(other),
             Value::Bool(value) => value.less(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "<", other),
         }
     }
 
@@ -147,7 +153,7 @@ impl Binary for Value {
             Value::Float(value) => value.less_equals(other),
             Value::String(value) => value.less_equals(other),
             Value::Bool(value) => value.less_equals(other),
-            _ => unimplemented!(),
+            _ => invalid_operation(self, "<=", other),
         }
     }
 }
@@ -162,6 +168,7 @@ impl Value {
             Value::Null => false,
             Value::Map(o) => !o.properties.is_empty(),
             Value::List(l) => !l.elements.is_empty(),
+            Value::Tuple(t) => !t.elements.is_empty(),
             _ => true,
         }
     }
@@ -170,8 +177,8 @@ impl Value {
         match self {
             Value::Map(m) => Some(m),
             Value::List(l) => Some(l),
-            Value::Instance(i) => todo!(),
-            Value::Class(c) => todo!(),
+            Value::Tuple(t) => Some(t),
+            Value::String(s) => Some(s),
             _ => None,
         }
     }
@@ -181,7 +188,9 @@ impl Value {
             Value::Instance(i) => Some(i),
             Value::Map(m) => Some(m),
             Value::List(l) => Some(l),
-            Value::Class(c) => todo!(),
+            Value::Tuple(t) => Some(t),
+            Value::String(s) => Some(s),
+            Value::Module(m) => Some(m),
             _ => None,
         }
     }
@@ -191,3 +200,201 @@ pub enum ValueError {
     InvalidOperation,
     InvalidType,
 }
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(int) => write!(f, "{}", int.0),
+            Value::Float(float) => write!(f, "{}", float.0),
+            Value::String(string) => write!(f, "{}", string.0),
+            Value::Bool(bool) => write!(f, "{}", bool.0),
+            Value::List(list) => {
+                let elements = list
+                    .elements
+                    .iter()
+                    .map(|element| element.borrow().repr())
+                    .collect::<Vec<String>>();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Tuple(tuple) => {
+                let elements = tuple
+                    .elements
+                    .iter()
+                    .map(|element| element.borrow().repr())
+                    .collect::<Vec<String>>();
+                match elements.as_slice() {
+                    [single] => write!(f, "({},)", single),
+                    _ => write!(f, "({})", elements.join(", ")),
+                }
+            }
+            Value::Map(map) => {
+                let properties = map
+                    .properties
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.repr(), value.borrow().repr()))
+                    .collect::<Vec<String>>();
+                write!(f, "{{{}}}", properties.join(", "))
+            }
+            Value::Function(_) | Value::Method(_) | Value::RustMethod(_) => write!(f, "<function>"),
+            Value::Class(class) => write!(f, "<class {}>", class.id),
+            Value::Instance(instance) => write!(f, "<instance {}>", instance.id),
+            Value::Module(module) => write!(f, "<module {}>", module.name),
+            Value::Reference(reference) => write!(f, "&{}", reference.name),
+            Value::Exception(exception) => write!(f, "{}", exception.0),
+            Value::Slice(slice) => {
+                let part = |bound: Option<i64>| bound.map(|b| b.to_string()).unwrap_or_default();
+                write!(f, "{}:{}:{}", part(slice.start), part(slice.end), part(slice.step))
+            }
+            Value::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl Value {
+    /// Whether the value may be used as a map key: immutable values, tuples of hashable values,
+    /// and values compared by identity (functions, classes, instances, ...).
+    pub fn is_hashable(&self) -> bool {
+        match self {
+            Value::Map(_) | Value::List(_) => false,
+            Value::Tuple(tuple) => tuple
+                .elements
+                .iter()
+                .all(|element| element.borrow().is_hashable()),
+            _ => true,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::String(_) => "string",
+            Value::Bool(_) => "bool",
+            Value::Map(_) => "map",
+            Value::List(_) => "list",
+            Value::Tuple(_) => "tuple",
+            Value::Function(_) => "function",
+            Value::Method(_) | Value::RustMethod(_) => "method",
+            Value::Class(_) => "class",
+            Value::Instance(_) => "instance",
+            Value::Exception(_) => "exception",
+            Value::Slice(_) => "slice",
+            Value::Module(_) => "module",
+            Value::Reference(_) => "reference",
+            Value::Null => "null",
+        }
+    }
+
+    /// `==` without needing the value mutably, so elements borrowed from a container can be
+    /// compared in place.
+    pub fn equals_value(&self, other: &Value) -> Result<bool, RuntimeException> {
+        match self {
+            Value::Int(_) | Value::Float(_) | Value::String(_) | Value::Bool(_) => {
+                Ok(self.clone().equals(other)?.is_truthy())
+            }
+            _ => Ok(structurally_equal(self, other, &mut Vec::new())),
+        }
+    }
+
+    /// The value itself for looking up a map key, or a `TypeError` if it is unhashable.
+    pub fn as_key(&self) -> Result<&Value, RuntimeException> {
+        if !self.is_hashable() {
+            return Err(exception::TYPE.runtime(format!(
+                "Unhashable type '{}' cannot be used as a map key",
+                self.type_name()
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Clone of the value for use as a map key, or a `TypeError` if it is unhashable.
+    pub fn to_key(&self) -> Result<Value, RuntimeException> {
+        self.as_key().cloned()
+    }
+
+    /// Elements produced by iterating the value: sequence items, string chars or map keys.
+    pub fn iter_elements(&self) -> Result<Vec<ValueRef>, RuntimeException> {
+        match self {
+            Value::List(list) => Ok(list.elements.clone()),
+            Value::Tuple(tuple) => Ok(tuple.elements.clone()),
+            Value::String(string) => Ok(string
+                .0
+                .chars()
+                .map(|c| Rc::new(RefCell::new(Value::string(c.to_string()))))
+                .collect()),
+            Value::Map(map) => Ok(map
+                .properties
+                .keys()
+                .map(|key| Rc::new(RefCell::new(key.clone())))
+                .collect()),
+            _ => {
+                Err(exception::TYPE
+                    .runtime(format!("'{}' value is not iterable", self.type_name())))
+            }
+        }
+    }
+
+    /// Like `Display`, but strings are quoted, as they appear inside containers.
+    pub fn repr(&self) -> String {
+        match self {
+            Value::String(string) => format!("{:?}", string.0),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// Element-wise equality of lists, tuples and maps. `comparing` holds the pairs of containers
+/// whose comparison is in progress; meeting one again means the values contain themselves, and
+/// the pair is taken as equal instead of being compared forever.
+/// Arithmetic and ordering on values only the primitives implement.
+fn invalid_operation(a: &Value, op: &str, b: &Value) -> Result<Value, RuntimeException> {
+    Err(exception::INVALID_OPERATION.runtime(format!(
+        "Invalid binary operation {} {} {}",
+        a.type_name(),
+        op,
+        b.type_name()
+    )))
+}
+
+fn structurally_equal(
+    a: &Value,
+    b: &Value,
+    comparing: &mut Vec<(*const RefCell<Value>, *const RefCell<Value>)>,
+) -> bool {
+    match (a, b) {
+        (Value::List(ListValue { elements: a }), Value::List(ListValue { elements: b }))
+        | (Value::Tuple(TupleValue { elements: a }), Value::Tuple(TupleValue { elements: b })) => {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| refs_equal(a, b, comparing))
+        }
+        (Value::Map(a), Value::Map(b)) => {
+            a.properties.len() == b.properties.len()
+                && a.properties.iter().all(|(key, a)| {
+                    b.properties
+                        .get(key)
+                        .is_some_and(|b| refs_equal(a, b, comparing))
+                })
+        }
+        _ => a == b,
+    }
+}
+
+fn refs_equal(
+    a: &ValueRef,
+    b: &ValueRef,
+    comparing: &mut Vec<(*const RefCell<Value>, *const RefCell<Value>)>,
+) -> bool {
+    if Rc::ptr_eq(a, b) {
+        return true;
+    }
+    let pair = (Rc::as_ptr(a), Rc::as_ptr(b));
+    if comparing.contains(&pair) {
+        return true;
+    }
+    comparing.push(pair);
+    let equal = structurally_equal(&a.borrow(), &b.borrow(), comparing);
+    comparing.pop();
+    equal
+}
@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use crate::compiler::code_object::CodeObject;
 use crate::parser::ExprNode;
-use crate::parser::nodes::{ListNode, MapNode};
+use crate::parser::nodes::{ListNode, MapEntry, MapNode};
 use crate::runtime::value::base::{RUNTIME_VALUE_ID, ValueError};
 use crate::runtime::value::methods::MethodFn;
 use crate::runtime::value::types::bool::BoolValue;
@@ -10,6 +10,7 @@ use crate::runtime::value::types::int::IntValue;
 use crate::runtime::value::types::rust_method::RustMethodValue;
 use crate::runtime::value::types::string::StringValue;
 use crate::runtime::value::*;
+use indexmap::IndexMap;
 use ordered_float::OrderedFloat;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -32,7 +33,7 @@ impl Value {
         Value::Bool(BoolValue(value))
     }
 
-    pub fn map(properties: HashMap<Value, ValueRef>) -> Value {
+    pub fn map(properties: IndexMap<Value, ValueRef>) -> Value {
         Value::Map(MapValue { properties })
     }
 
@@ -40,7 +41,33 @@ impl Value {
         Value::List(ListValue { elements })
     }
 
-    pub fn function(parameters: Vec<String>, body: CodeObject) -> Value {
+    pub fn tuple(elements: Vec<ValueRef>) -> Value {
+        Value::Tuple(TupleValue { elements })
+    }
+
+    pub(crate) fn reference(
+        name: String,
+        slots: Rc<RefCell<Vec<StackValue>>>,
+        index: usize,
+    ) -> Value {
+        Value::Reference(ReferenceValue { name, slots, index })
+    }
+
+    pub fn slice(start: Option<i64>, end: Option<i64>, step: Option<i64>) -> Value {
+        Value::Slice(SliceValue { start, end, step })
+    }
+
+    pub fn module(name: String, path: String, code_object: &CodeObject) -> Value {
+        Value::Module(ModuleValue {
+            id: RUNTIME_VALUE_ID.next(),
+            name,
+            path,
+            code_object_id: code_object.id,
+            variable_index_lookup: code_object.variable_index_lookup.clone(),
+        })
+    }
+
+    pub fn function(parameters: Vec<String>, body: Rc<CodeObject>) -> Value {
         Value::Function(FunctionValue {
             id: RUNTIME_VALUE_ID.next(),
             parameters,
@@ -56,7 +83,7 @@ impl Value {
         })
     }
 
-    pub fn class(parent: Option<ValueRef>, body: CodeObject) -> Value {
+    pub fn class(parent: Option<ValueRef>, body: Rc<CodeObject>) -> Value {
         Value::Class(ClassValue {
             id: RUNTIME_VALUE_ID.next(),
             parent, // will always be ClassValue
@@ -73,8 +100,11 @@ impl Value {
     }
 
     pub fn try_const_from_map(node: MapNode) -> Result<Value, ValueError> {
-        let mut obj_props = HashMap::new();
-        for obj_prop in node.properties {
+        let mut obj_props = IndexMap::new();
+        for entry in node.properties {
+            let MapEntry::Property(obj_prop) = entry else {
+                return Err(ValueError::InvalidType);
+            };
             obj_props.insert(
                 Value::from_expr(obj_prop.key)?,
                 Rc::new(RefCell::new(Value::from_expr(obj_prop.value)?)),
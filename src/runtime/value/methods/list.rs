@@ -1,9 +1,12 @@
+use crate::runtime::Runtime;
 use crate::runtime::utils::extract_int_ref;
+use crate::runtime::utils::value_to_ref;
 use crate::runtime::value::exception;
 use crate::runtime::value::methods::traits::{MethodFn, MethodProvider};
-use crate::runtime::value::methods::utils::arg_check;
+use crate::runtime::value::methods::utils::{arg_check, arg_range_check, bool_arg, order_values};
 use crate::runtime::value::{ListValue, RuntimeException, Value, ValueRef};
 use std::cell::RefCell;
+use std::cmp::Ordering;
 use std::rc::Rc;
 
 impl MethodProvider for ListValue {
@@ -16,6 +19,13 @@ impl MethodProvider for ListValue {
             "remove" => Some(ListValue::method_remove),
             "len" => Some(ListValue::method_len),
             "is_empty" => Some(ListValue::method_is_empty),
+            "sort" => Some(ListValue::method_sort),
+            "map" => Some(ListValue::method_map),
+            "filter" => Some(ListValue::method_filter),
+            "reduce" => Some(ListValue::method_reduce),
+            "any" => Some(ListValue::method_any),
+            "all" => Some(ListValue::method_all),
+            "to_tuple" => Some(ListValue::method_to_tuple),
             _ => None,
         }
     }
@@ -23,6 +33,7 @@ impl MethodProvider for ListValue {
 
 impl ListValue {
     pub fn method_push(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -37,6 +48,7 @@ impl ListValue {
     }
 
     pub fn method_pop(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -48,6 +60,7 @@ impl ListValue {
     }
 
     pub fn method_pop_at(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -66,6 +79,7 @@ impl ListValue {
     }
 
     pub fn method_get(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -84,6 +98,7 @@ impl ListValue {
     }
 
     pub fn method_remove(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -104,6 +119,7 @@ impl ListValue {
     }
 
     pub fn method_len(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -117,6 +133,7 @@ impl ListValue {
     }
 
     pub fn method_is_empty(
+        _runtime: &mut Runtime,
         list_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -128,4 +145,166 @@ impl ListValue {
             _ => unreachable!(),
         }
     }
+
+    /// Sorts in place by the elements (or `key(element)`), stable, optionally `reverse`d.
+    pub fn method_sort(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 0, 2, "List.sort")?;
+        let key = args
+            .first()
+            .filter(|key| !matches!(*key.borrow(), Value::Null));
+        let reverse = match args.get(1) {
+            Some(reverse) => bool_arg(reverse, "List.sort")?,
+            None => false,
+        };
+        let elements = list_elements(list_value);
+        let mut keyed = Vec::with_capacity(elements.len());
+        for element in elements {
+            let sort_key = match key {
+                Some(key) => runtime.call_value(key, vec![element.clone()])?,
+                None => element.clone(),
+            };
+            keyed.push((sort_key, element));
+        }
+        let mut order_error = None;
+        keyed.sort_by(|(a, _), (b, _)| {
+            let ordering = order_values(a, b).unwrap_or_else(|err| {
+                order_error.get_or_insert(err);
+                Ordering::Equal
+            });
+            if reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        if let Some(err) = order_error {
+            return Err(err);
+        }
+        match &mut *list_value.borrow_mut() {
+            Value::List(list) => {
+                list.elements = keyed.into_iter().map(|(_, element)| element).collect();
+                Ok(None)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_map(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "List.map")?;
+        let mapped = list_elements(list_value)
+            .into_iter()
+            .map(|element| runtime.call_value(args[0], vec![element]))
+            .collect::<Result<Vec<ValueRef>, RuntimeException>>()?;
+        Ok(Some(value_to_ref(Value::list(mapped))))
+    }
+
+    pub fn method_filter(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "List.filter")?;
+        let mut filtered = Vec::new();
+        for element in list_elements(list_value) {
+            if runtime
+                .call_value(args[0], vec![element.clone()])?
+                .borrow()
+                .is_truthy()
+            {
+                filtered.push(element);
+            }
+        }
+        Ok(Some(value_to_ref(Value::list(filtered))))
+    }
+
+    /// Folds left with `function(accumulator, element)`, starting from `initial` or the first element.
+    pub fn method_reduce(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 1, 2, "List.reduce")?;
+        let mut elements = list_elements(list_value).into_iter();
+        let mut accumulator =
+            match args.get(1) {
+                Some(initial) => (*initial).clone(),
+                None => {
+                    elements.next().ok_or(exception::VALUE.runtime(
+                        "List.reduce of an empty list with no initial value".to_string(),
+                    ))?
+                }
+            };
+        for element in elements {
+            accumulator = runtime.call_value(args[0], vec![accumulator, element])?;
+        }
+        Ok(Some(accumulator))
+    }
+
+    /// Whether any element (or `predicate(element)`) is truthy.
+    pub fn method_any(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 0, 1, "List.any")?;
+        for element in list_elements(list_value) {
+            if truthy_or_predicate(runtime, args.first(), element)? {
+                return Ok(Some(value_to_ref(Value::bool(true))));
+            }
+        }
+        Ok(Some(value_to_ref(Value::bool(false))))
+    }
+
+    /// Whether every element (or `predicate(element)`) is truthy.
+    pub fn method_all(
+        runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 0, 1, "List.all")?;
+        for element in list_elements(list_value) {
+            if !truthy_or_predicate(runtime, args.first(), element)? {
+                return Ok(Some(value_to_ref(Value::bool(false))));
+            }
+        }
+        Ok(Some(value_to_ref(Value::bool(true))))
+    }
+
+    pub fn method_to_tuple(
+        _runtime: &mut Runtime,
+        list_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "List.to_tuple")?;
+        Ok(Some(value_to_ref(Value::tuple(list_elements(list_value)))))
+    }
+}
+
+/// Snapshot of the elements, so the list isn't borrowed while script callbacks run.
+fn list_elements(list_value: &ValueRef) -> Vec<ValueRef> {
+    match &*list_value.borrow() {
+        Value::List(list) => list.elements.clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn truthy_or_predicate(
+    runtime: &mut Runtime,
+    predicate: Option<&&ValueRef>,
+    element: ValueRef,
+) -> Result<bool, RuntimeException> {
+    let result = match predicate {
+        Some(predicate) => runtime.call_value(predicate, vec![element])?,
+        None => element,
+    };
+    let is_truthy = result.borrow().is_truthy();
+    Ok(is_truthy)
 }
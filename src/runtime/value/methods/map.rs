@@ -1,6 +1,8 @@
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
 use crate::runtime::value::exception;
 use crate::runtime::value::methods::traits::{MethodFn, MethodProvider};
-use crate::runtime::value::methods::utils::arg_check;
+use crate::runtime::value::methods::utils::{arg_check, arg_range_check};
 use crate::runtime::value::{MapValue, RuntimeException, Value, ValueRef};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -13,6 +15,15 @@ impl MethodProvider for MapValue {
             "remove" => Some(MapValue::method_remove),
             "len" => Some(MapValue::method_len),
             "is_empty" => Some(MapValue::method_is_empty),
+            "map_values" => Some(MapValue::method_map_values),
+            "keys" => Some(MapValue::method_keys),
+            "values" => Some(MapValue::method_values),
+            "items" => Some(MapValue::method_items),
+            "contains" => Some(MapValue::method_contains),
+            "set_default" => Some(MapValue::method_set_default),
+            "update" => Some(MapValue::method_update),
+            "pop" => Some(MapValue::method_pop),
+            "clear" => Some(MapValue::method_clear),
             _ => None,
         }
     }
@@ -20,6 +31,7 @@ impl MethodProvider for MapValue {
 
 impl MapValue {
     pub fn method_insert(
+        _runtime: &mut Runtime,
         map_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -27,21 +39,28 @@ impl MapValue {
         match &mut *map_value.borrow_mut() {
             Value::Map(map) => {
                 map.properties
-                    .insert(args[0].borrow().clone(), args[1].clone());
+                    .insert(args[0].borrow().to_key()?, args[1].clone());
             }
             _ => unreachable!(),
         }
         Ok(None)
     }
 
+    /// Value for `key`; a missing key returns `default` if given, otherwise raises.
     pub fn method_get(
+        _runtime: &mut Runtime,
         map_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
-        arg_check(args.len(), 1, "Map.get")?;
+        arg_range_check(args.len(), 1, 2, "Map.get")?;
         match &mut *map_value.borrow_mut() {
             Value::Map(map) => {
-                let key = &*args[0].borrow();
+                let key = args[0].borrow();
+                let key = key.as_key()?;
+                if let Some(default) = args.get(1) {
+                    let result = map.properties.get(key).unwrap_or(default);
+                    return Ok(Some(result.clone()));
+                }
                 let result = map.properties.get(key).ok_or(
                     exception::KEY_ERROR.runtime(format!("Entry with key {:?} not found", key)),
                 )?;
@@ -52,14 +71,16 @@ impl MapValue {
     }
 
     pub fn method_remove(
+        _runtime: &mut Runtime,
         map_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
         arg_check(args.len(), 1, "Map.remove")?;
         match &mut *map_value.borrow_mut() {
             Value::Map(map) => {
-                let key = &*args[0].borrow();
-                map.properties.remove(key).ok_or(
+                let key = args[0].borrow();
+                let key = key.as_key()?;
+                map.properties.shift_remove(key).ok_or(
                     exception::KEY_ERROR.runtime(format!("Entry with key {:?} not found", key)),
                 )?;
                 Ok(None)
@@ -69,6 +90,7 @@ impl MapValue {
     }
 
     pub fn method_len(
+        _runtime: &mut Runtime,
         map_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -82,6 +104,7 @@ impl MapValue {
     }
 
     pub fn method_is_empty(
+        _runtime: &mut Runtime,
         map_value: &ValueRef,
         args: &[&ValueRef],
     ) -> Result<Option<ValueRef>, RuntimeException> {
@@ -93,4 +116,184 @@ impl MapValue {
             _ => unreachable!(),
         }
     }
+
+    /// New map with the same keys and every value replaced by `function(value)`.
+    pub fn method_map_values(
+        runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "Map.map_values")?;
+        // snapshot, so the map isn't borrowed while script callbacks run
+        let properties: Vec<(Value, ValueRef)> = match &*map_value.borrow() {
+            Value::Map(map) => map
+                .properties
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            _ => unreachable!(),
+        };
+        let mapped = properties
+            .into_iter()
+            .map(|(key, value)| Ok((key, runtime.call_value(args[0], vec![value])?)))
+            .collect::<Result<_, RuntimeException>>()?;
+        Ok(Some(value_to_ref(Value::map(mapped))))
+    }
+
+    pub fn method_keys(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Map.keys")?;
+        match &*map_value.borrow() {
+            Value::Map(map) => {
+                let keys = map
+                    .properties
+                    .keys()
+                    .map(|key| value_to_ref(key.clone()))
+                    .collect();
+                Ok(Some(value_to_ref(Value::list(keys))))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_values(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Map.values")?;
+        match &*map_value.borrow() {
+            Value::Map(map) => {
+                let values = map.properties.values().cloned().collect();
+                Ok(Some(value_to_ref(Value::list(values))))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// `[key, value]` pairs in insertion order.
+    pub fn method_items(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Map.items")?;
+        match &*map_value.borrow() {
+            Value::Map(map) => {
+                let items = map
+                    .properties
+                    .iter()
+                    .map(|(key, value)| {
+                        value_to_ref(Value::list(vec![value_to_ref(key.clone()), value.clone()]))
+                    })
+                    .collect();
+                Ok(Some(value_to_ref(Value::list(items))))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_contains(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "Map.contains")?;
+        match &*map_value.borrow() {
+            Value::Map(map) => {
+                let contains = map.properties.contains_key(args[0].borrow().as_key()?);
+                Ok(Some(value_to_ref(Value::bool(contains))))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Inserts `default` if `key` is missing, then returns the value for `key`.
+    pub fn method_set_default(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 2, "Map.set_default")?;
+        match &mut *map_value.borrow_mut() {
+            Value::Map(map) => {
+                let value = map
+                    .properties
+                    .entry(args[0].borrow().to_key()?)
+                    .or_insert_with(|| args[1].clone());
+                Ok(Some(value.clone()))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Inserts every entry of another map, overwriting existing keys.
+    pub fn method_update(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "Map.update")?;
+        let entries: Vec<(Value, ValueRef)> = match &*args[0].borrow() {
+            Value::Map(other) => other
+                .properties
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            other => {
+                return Err(
+                    exception::TYPE.runtime(format!("Map.update expected a map, got {:?}", other))
+                );
+            }
+        };
+        match &mut *map_value.borrow_mut() {
+            Value::Map(map) => {
+                map.properties.extend(entries);
+                Ok(None)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Removes `key` and returns its value; a missing key returns `default` if given, otherwise raises.
+    pub fn method_pop(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 1, 2, "Map.pop")?;
+        match &mut *map_value.borrow_mut() {
+            Value::Map(map) => {
+                let key = args[0].borrow();
+                let key = key.as_key()?;
+                match (map.properties.shift_remove(key), args.get(1)) {
+                    (Some(value), _) => Ok(Some(value)),
+                    (None, Some(default)) => Ok(Some((*default).clone())),
+                    (None, None) => {
+                        Err(exception::KEY_ERROR
+                            .runtime(format!("Entry with key {:?} not found", key)))
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_clear(
+        _runtime: &mut Runtime,
+        map_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Map.clear")?;
+        match &mut *map_value.borrow_mut() {
+            Value::Map(map) => {
+                map.properties.clear();
+                Ok(None)
+            }
+            _ => unreachable!(),
+        }
+    }
 }
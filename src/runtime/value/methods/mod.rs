@@ -1,6 +1,8 @@
 mod list;
 mod map;
+mod string;
 mod traits;
-mod utils;
+mod tuple;
+pub(crate) mod utils;
 
 pub use traits::*;
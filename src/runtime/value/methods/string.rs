@@ -0,0 +1,271 @@
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
+use crate::runtime::value::exception;
+use crate::runtime::value::methods::traits::{MethodFn, MethodProvider};
+use crate::runtime::value::methods::utils::{arg_check, arg_range_check, int_arg, string_arg};
+use crate::runtime::value::types::string::StringValue;
+use crate::runtime::value::{RuntimeException, Value, ValueRef};
+
+impl MethodProvider for StringValue {
+    fn get_method(name: &str) -> Option<MethodFn> {
+        match name {
+            "len" => Some(StringValue::method_len),
+            "upper" => Some(StringValue::method_upper),
+            "lower" => Some(StringValue::method_lower),
+            "strip" => Some(StringValue::method_strip),
+            "split" => Some(StringValue::method_split),
+            "join" => Some(StringValue::method_join),
+            "replace" => Some(StringValue::method_replace),
+            "find" => Some(StringValue::method_find),
+            "starts_with" => Some(StringValue::method_starts_with),
+            "ends_with" => Some(StringValue::method_ends_with),
+            "contains" => Some(StringValue::method_contains),
+            "chars" => Some(StringValue::method_chars),
+            "repeat" => Some(StringValue::method_repeat),
+            "format" => Some(StringValue::method_format),
+            _ => None,
+        }
+    }
+}
+
+fn caller_string(string_value: &ValueRef) -> String {
+    match &*string_value.borrow() {
+        Value::String(string) => string.0.clone(),
+        _ => unreachable!(),
+    }
+}
+
+fn string_list<I: Iterator<Item = String>>(strings: I) -> Value {
+    Value::list(strings.map(|s| value_to_ref(Value::string(s))).collect())
+}
+
+impl StringValue {
+    pub fn method_len(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "String.len")?;
+        let length = caller_string(string_value).chars().count();
+        Ok(Some(value_to_ref(Value::int(length as i64))))
+    }
+
+    pub fn method_upper(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "String.upper")?;
+        let upper = caller_string(string_value).to_uppercase();
+        Ok(Some(value_to_ref(Value::string(upper))))
+    }
+
+    pub fn method_lower(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "String.lower")?;
+        let lower = caller_string(string_value).to_lowercase();
+        Ok(Some(value_to_ref(Value::string(lower))))
+    }
+
+    pub fn method_strip(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "String.strip")?;
+        let stripped = caller_string(string_value).trim().to_string();
+        Ok(Some(value_to_ref(Value::string(stripped))))
+    }
+
+    /// Splits on whitespace without arguments, or on the given separator.
+    pub fn method_split(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_range_check(args.len(), 0, 1, "String.split")?;
+        let string = caller_string(string_value);
+        let parts = match args.first() {
+            None => string_list(string.split_whitespace().map(String::from)),
+            Some(separator) => {
+                let separator = string_arg(separator, "String.split")?;
+                if separator.is_empty() {
+                    return Err(exception::VALUE.runtime("Empty separator".to_string()));
+                }
+                string_list(string.split(separator.as_str()).map(String::from))
+            }
+        };
+        Ok(Some(value_to_ref(parts)))
+    }
+
+    pub fn method_join(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.join")?;
+        let separator = caller_string(string_value);
+        let parts = match &*args[0].borrow() {
+            Value::List(list) => list
+                .elements
+                .iter()
+                .map(|element| string_arg(element, "String.join"))
+                .collect::<Result<Vec<String>, RuntimeException>>()?,
+            other => {
+                return Err(exception::TYPE
+                    .runtime(format!("String.join expected a list, got {:?}", other)));
+            }
+        };
+        Ok(Some(value_to_ref(Value::string(parts.join(&separator)))))
+    }
+
+    pub fn method_replace(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 2, "String.replace")?;
+        let from = string_arg(args[0], "String.replace")?;
+        let to = string_arg(args[1], "String.replace")?;
+        let replaced = caller_string(string_value).replace(from.as_str(), to.as_str());
+        Ok(Some(value_to_ref(Value::string(replaced))))
+    }
+
+    /// Char index of the first occurrence, or -1.
+    pub fn method_find(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.find")?;
+        let needle = string_arg(args[0], "String.find")?;
+        let string = caller_string(string_value);
+        let index = string
+            .find(needle.as_str())
+            .map(|byte_index| string[..byte_index].chars().count() as i64)
+            .unwrap_or(-1);
+        Ok(Some(value_to_ref(Value::int(index))))
+    }
+
+    pub fn method_starts_with(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.starts_with")?;
+        let prefix = string_arg(args[0], "String.starts_with")?;
+        let result = caller_string(string_value).starts_with(prefix.as_str());
+        Ok(Some(value_to_ref(Value::bool(result))))
+    }
+
+    pub fn method_ends_with(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.ends_with")?;
+        let suffix = string_arg(args[0], "String.ends_with")?;
+        let result = caller_string(string_value).ends_with(suffix.as_str());
+        Ok(Some(value_to_ref(Value::bool(result))))
+    }
+
+    pub fn method_contains(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.contains")?;
+        let needle = string_arg(args[0], "String.contains")?;
+        let result = caller_string(string_value).contains(needle.as_str());
+        Ok(Some(value_to_ref(Value::bool(result))))
+    }
+
+    pub fn method_chars(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "String.chars")?;
+        let chars = string_list(caller_string(string_value).chars().map(String::from));
+        Ok(Some(value_to_ref(chars)))
+    }
+
+    pub fn method_repeat(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 1, "String.repeat")?;
+        let count = int_arg(args[0], "String.repeat")?;
+        if count < 0 {
+            return Err(exception::VALUE.runtime(format!(
+                "String.repeat count cannot be negative ({})",
+                count
+            )));
+        }
+        let repeated = caller_string(string_value).repeat(count as usize);
+        Ok(Some(value_to_ref(Value::string(repeated))))
+    }
+
+    /// Fills `{}` placeholders in order and `{n}` placeholders by position; `{{`/`}}` escape.
+    pub fn method_format(
+        _runtime: &mut Runtime,
+        string_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        let template = caller_string(string_value);
+        let mut formatted = String::new();
+        let mut next_arg = 0;
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    formatted.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    formatted.push('}');
+                }
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(exception::VALUE
+                                    .runtime("Unclosed placeholder in format string".to_string()));
+                            }
+                        }
+                    }
+                    let arg_index = if placeholder.is_empty() {
+                        next_arg += 1;
+                        next_arg - 1
+                    } else {
+                        placeholder.trim().parse::<usize>().map_err(|_| {
+                            exception::VALUE
+                                .runtime(format!("Invalid placeholder {{{}}}", placeholder))
+                        })?
+                    };
+                    let arg =
+                        args.get(arg_index)
+                            .ok_or(exception::INDEX_ERROR.runtime(format!(
+                                "Format placeholder {} out of range ({} arguments given)",
+                                arg_index,
+                                args.len()
+                            )))?;
+                    formatted.push_str(&arg.borrow().to_string());
+                }
+                '}' => {
+                    return Err(exception::VALUE.runtime("Single '}' in format string".to_string()));
+                }
+                c => formatted.push(c),
+            }
+        }
+        Ok(Some(value_to_ref(Value::string(formatted))))
+    }
+}
@@ -1,6 +1,9 @@
+use crate::runtime::Runtime;
 use crate::runtime::value::{RuntimeException, ValueRef};
 
-pub type MethodFn = fn(&ValueRef, &[&ValueRef]) -> Result<Option<ValueRef>, RuntimeException>;
+/// Native method: receives the runtime (to call back into script code), the caller and the arguments.
+pub type MethodFn =
+    fn(&mut Runtime, &ValueRef, &[&ValueRef]) -> Result<Option<ValueRef>, RuntimeException>;
 
 pub trait MethodProvider {
     fn get_method(name: &str) -> Option<MethodFn>
@@ -0,0 +1,55 @@
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
+use crate::runtime::value::methods::traits::{MethodFn, MethodProvider};
+use crate::runtime::value::methods::utils::arg_check;
+use crate::runtime::value::types::tuple::TupleValue;
+use crate::runtime::value::{RuntimeException, Value, ValueRef};
+
+impl MethodProvider for TupleValue {
+    fn get_method(name: &str) -> Option<MethodFn> {
+        match name {
+            "len" => Some(TupleValue::method_len),
+            "is_empty" => Some(TupleValue::method_is_empty),
+            "to_list" => Some(TupleValue::method_to_list),
+            _ => None,
+        }
+    }
+}
+
+impl TupleValue {
+    pub fn method_len(
+        _runtime: &mut Runtime,
+        tuple_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Tuple.len")?;
+        match &*tuple_value.borrow() {
+            Value::Tuple(tuple) => Ok(Some(value_to_ref(Value::int(tuple.elements.len() as i64)))),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_is_empty(
+        _runtime: &mut Runtime,
+        tuple_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Tuple.is_empty")?;
+        match &*tuple_value.borrow() {
+            Value::Tuple(tuple) => Ok(Some(value_to_ref(Value::bool(tuple.elements.is_empty())))),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn method_to_list(
+        _runtime: &mut Runtime,
+        tuple_value: &ValueRef,
+        args: &[&ValueRef],
+    ) -> Result<Option<ValueRef>, RuntimeException> {
+        arg_check(args.len(), 0, "Tuple.to_list")?;
+        match &*tuple_value.borrow() {
+            Value::Tuple(tuple) => Ok(Some(value_to_ref(Value::list(tuple.elements.clone())))),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,5 +1,7 @@
-use crate::runtime::value::RuntimeException;
 use crate::runtime::value::exception;
+use crate::runtime::value::traits::Binary;
+use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use std::cmp::Ordering;
 
 pub fn arg_check(
     arg_count: usize,
@@ -13,3 +15,65 @@ pub fn arg_check(
             name, expected_count, arg_count
         )))
 }
+
+pub fn arg_range_check(
+    arg_count: usize,
+    min_count: usize,
+    max_count: usize,
+    name: &str,
+) -> Result<(), RuntimeException> {
+    (min_count..=max_count)
+        .contains(&arg_count)
+        .then_some(())
+        .ok_or(exception::ARGUMENT.runtime(format!(
+            "{} takes {} to {} arguments ({} given)",
+            name, min_count, max_count, arg_count
+        )))
+}
+
+pub fn string_arg(value: &ValueRef, name: &str) -> Result<String, RuntimeException> {
+    match &*value.borrow() {
+        Value::String(string) => Ok(string.0.clone()),
+        other => Err(exception::TYPE.runtime(format!(
+            "{} expected a string argument, got {:?}",
+            name, other
+        ))),
+    }
+}
+
+pub fn int_arg(value: &ValueRef, name: &str) -> Result<i64, RuntimeException> {
+    match &*value.borrow() {
+        Value::Int(int) => Ok(int.0),
+        other => Err(exception::TYPE.runtime(format!(
+            "{} expected an int argument, got {:?}",
+            name, other
+        ))),
+    }
+}
+
+pub fn bool_arg(value: &ValueRef, name: &str) -> Result<bool, RuntimeException> {
+    match &*value.borrow() {
+        Value::Bool(bool) => Ok(bool.0),
+        other => Err(exception::TYPE.runtime(format!(
+            "{} expected a bool argument, got {:?}",
+            name, other
+        ))),
+    }
+}
+
+/// Total order for sorting; only numbers and strings can be ordered.
+pub fn order_values(a: &ValueRef, b: &ValueRef) -> Result<Ordering, RuntimeException> {
+    let (a, b) = (a.borrow(), b.borrow());
+    let comparable =
+        |value: &Value| matches!(value, Value::Int(_) | Value::Float(_) | Value::String(_));
+    if !comparable(&a) || !comparable(&b) {
+        return Err(exception::TYPE.runtime(format!("Cannot order {:?} and {:?}", a, b)));
+    }
+    if a.clone().less(&b)?.is_truthy() {
+        Ok(Ordering::Less)
+    } else if b.clone().less(&a)?.is_truthy() {
+        Ok(Ordering::Greater)
+    } else {
+        Ok(Ordering::Equal)
+    }
+}
@@ -3,11 +3,15 @@ mod binary;
 mod constructors;
 mod indexable;
 mod methods;
+mod stack;
 mod types;
 mod utils;
 
 pub use base::Value;
+pub(crate) use indexable::get_class_attr;
 pub use methods::MethodFn;
+pub(crate) use methods::utils::arg_check;
+pub(crate) use stack::StackValue;
 use std::cell::RefCell;
 use std::rc::Rc;
 pub use types::class::ClassValue;
@@ -18,7 +22,11 @@ pub use types::instance::InstanceValue;
 pub use types::list::ListValue;
 pub use types::map::MapValue;
 pub use types::method::MethodValue;
+pub use types::module::ModuleValue;
+pub use types::reference::ReferenceValue;
 pub use types::rust_method::*;
+pub use types::slice::SliceValue;
+pub use types::tuple::TupleValue;
 
 pub type ValueRef = Rc<RefCell<Value>>;
 
@@ -0,0 +1,142 @@
+use crate::compiler::ByteOp;
+use crate::compiler::byte_operations::ByteComparisonOp;
+use crate::runtime::value::{Value, ValueRef};
+use ordered_float::OrderedFloat;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A slot of the VM stack or of a frame. Null, bools, ints and floats are held inline, every
+/// other value is shared behind its `Rc`. Primitives are never mutated in place, so copying
+/// them out of a `ValueRef` (and boxing them again when stored into a container) cannot be
+/// observed from script.
+#[derive(Clone, Debug)]
+pub(crate) enum StackValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Object(ValueRef),
+}
+
+impl StackValue {
+    /// The value behind a shared reference, boxing primitives.
+    pub(crate) fn into_ref(self) -> ValueRef {
+        match self {
+            StackValue::Object(value) => value,
+            primitive => Rc::new(RefCell::new(primitive.to_value())),
+        }
+    }
+
+    pub(crate) fn to_ref(&self) -> ValueRef {
+        self.clone().into_ref()
+    }
+
+    /// Runs `f` on the value, borrowing objects and materializing primitives in place.
+    pub(crate) fn with_value<R>(&self, f: impl FnOnce(&Value) -> R) -> R {
+        match self {
+            StackValue::Object(value) => f(&value.borrow()),
+            primitive => f(&primitive.to_value()),
+        }
+    }
+
+    pub(crate) fn is_truthy(&self) -> bool {
+        match self {
+            StackValue::Null => false,
+            StackValue::Bool(value) => *value,
+            StackValue::Int(value) => *value != 0,
+            StackValue::Float(value) => *value != 0.0,
+            StackValue::Object(value) => value.borrow().is_truthy(),
+        }
+    }
+
+    /// An owned copy of the value, objects are cloned out of their cell.
+    pub(crate) fn to_value(&self) -> Value {
+        match self {
+            StackValue::Null => Value::Null,
+            StackValue::Bool(value) => Value::bool(*value),
+            StackValue::Int(value) => Value::int(*value),
+            StackValue::Float(value) => Value::float(*value),
+            StackValue::Object(value) => value.borrow().clone(),
+        }
+    }
+
+    /// `+`, `-` and `*` on two inline numbers, computed without materializing `Value`s. Gives
+    /// the same results as the `Binary` impls and `None` for anything it does not cover.
+    pub(crate) fn arithmetic(op: ByteOp, a: &StackValue, b: &StackValue) -> Option<StackValue> {
+        let (a, b) = match (a, b) {
+            (StackValue::Int(a), StackValue::Int(b)) => {
+                return match op {
+                    ByteOp::Add => Some(StackValue::Int(a + b)),
+                    ByteOp::Sub => Some(StackValue::Int(a - b)),
+                    ByteOp::Mul => Some(StackValue::Int(a * b)),
+                    _ => None,
+                };
+            }
+            (StackValue::Int(a), StackValue::Float(b)) => (*a as f64, *b),
+            (StackValue::Float(a), StackValue::Int(b)) => (*a, *b as f64),
+            (StackValue::Float(a), StackValue::Float(b)) => (*a, *b),
+            _ => return None,
+        };
+        match op {
+            ByteOp::Add => Some(StackValue::Float(a + b)),
+            ByteOp::Sub => Some(StackValue::Float(a - b)),
+            ByteOp::Mul => Some(StackValue::Float(a * b)),
+            _ => None,
+        }
+    }
+
+    /// Ordering comparisons and equality of two inline numbers, see `arithmetic`.
+    pub(crate) fn compare(
+        op: ByteComparisonOp,
+        a: &StackValue,
+        b: &StackValue,
+    ) -> Option<StackValue> {
+        let ordering = match (a, b) {
+            (StackValue::Int(a), StackValue::Int(b)) => a.partial_cmp(b),
+            (StackValue::Int(a), StackValue::Float(b)) => (*a as f64).partial_cmp(b),
+            (StackValue::Float(a), StackValue::Int(b)) => a.partial_cmp(&(*b as f64)),
+            // floats compare among themselves as `FloatValue` does, NaN included
+            (StackValue::Float(a), StackValue::Float(b)) => {
+                OrderedFloat(*a).partial_cmp(&OrderedFloat(*b))
+            }
+            _ => return None,
+        };
+        let result = match op {
+            ByteComparisonOp::Equal => ordering.is_some_and(|ordering| ordering.is_eq()),
+            ByteComparisonOp::Greater => ordering.is_some_and(|ordering| ordering.is_gt()),
+            ByteComparisonOp::GreaterEqual => ordering.is_some_and(|ordering| ordering.is_ge()),
+            ByteComparisonOp::Less => ordering.is_some_and(|ordering| ordering.is_lt()),
+            ByteComparisonOp::LessEqual => ordering.is_some_and(|ordering| ordering.is_le()),
+            _ => return None,
+        };
+        Some(StackValue::Bool(result))
+    }
+
+    fn from_primitive(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(StackValue::Null),
+            Value::Bool(value) => Some(StackValue::Bool(value.0)),
+            Value::Int(value) => Some(StackValue::Int(value.0)),
+            Value::Float(value) => Some(StackValue::Float(value.0.0)),
+            _ => None,
+        }
+    }
+}
+
+impl From<Value> for StackValue {
+    fn from(value: Value) -> Self {
+        StackValue::from_primitive(&value)
+            .unwrap_or_else(|| StackValue::Object(Rc::new(RefCell::new(value))))
+    }
+}
+
+impl From<ValueRef> for StackValue {
+    fn from(value: ValueRef) -> Self {
+        // a value borrowed mutably right now is a container being written to
+        let primitive = value
+            .try_borrow()
+            .ok()
+            .and_then(|borrowed| StackValue::from_primitive(&borrowed));
+        primitive.unwrap_or(StackValue::Object(value))
+    }
+}
@@ -1,12 +1,13 @@
 use crate::compiler::code_object::CodeObject;
 use crate::runtime::value::ValueRef;
 use std::hash::Hash;
+use std::rc::Rc;
 
 #[derive(Eq, Clone, Debug)]
 pub struct ClassValue {
     pub id: usize,
     pub parent: Option<ValueRef>, // Will always be ClassValue
-    pub body: CodeObject,
+    pub body: Rc<CodeObject>,     // shared with the class values made from it at runtime
 }
 
 impl Hash for ClassValue {
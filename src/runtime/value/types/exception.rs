@@ -8,6 +8,7 @@ impl ExceptionValue {
         RuntimeException {
             kind: self.clone(),
             message,
+            traceback: Vec::new(),
         }
     }
 }
@@ -16,6 +17,15 @@ impl ExceptionValue {
 pub struct RuntimeException {
     pub kind: ExceptionValue,
     pub message: String,
+    pub traceback: Vec<TraceEntry>, // frames the exception unwound, innermost first
+}
+
+/// Where a frame was when an exception passed through it.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct TraceEntry {
+    pub file: String,
+    pub line: Option<usize>,
+    pub name: String,
 }
 
 pub static VALUE: ExceptionValue = ExceptionValue(Cow::Borrowed("ValueError"));
@@ -24,4 +34,6 @@ pub static ATTRIBUTE: ExceptionValue = ExceptionValue(Cow::Borrowed("AttributeEr
 pub static ARGUMENT: ExceptionValue = ExceptionValue(Cow::Borrowed("ArgumentError"));
 pub static INDEX_ERROR: ExceptionValue = ExceptionValue(Cow::Borrowed("IndexError"));
 pub static KEY_ERROR: ExceptionValue = ExceptionValue(Cow::Borrowed("KeyError"));
+pub static IMPORT: ExceptionValue = ExceptionValue(Cow::Borrowed("ImportError"));
+pub static RECURSION: ExceptionValue = ExceptionValue(Cow::Borrowed("RecursionError"));
 pub static INVALID_OPERATION: ExceptionValue = ExceptionValue(Cow::Borrowed("InvalidOperation"));
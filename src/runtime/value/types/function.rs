@@ -1,15 +1,16 @@
 use crate::compiler::code_object::CodeObject;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 #[derive(Eq, PartialEq, Hash, Clone)]
 pub struct FunctionValue {
     pub id: usize,
     pub parameters: Vec<String>,
-    pub body: CodeObject,
+    pub body: Rc<CodeObject>, // shared by every method bound from it
 }
 
 impl FunctionValue {
-    pub fn new(parameters: Vec<String>, body: CodeObject) -> Self {
+    pub fn new(parameters: Vec<String>, body: Rc<CodeObject>) -> Self {
         Self {
             id: crate::runtime::value::base::RUNTIME_VALUE_ID.next(),
             parameters,
@@ -24,7 +25,10 @@ impl Debug for FunctionValue {
             f,
             "<{}>{:?}",
             self.parameters.join(", "),
-            self.body.operations
+            self.body
+                .instructions()
+                .map(|(_, op)| op)
+                .collect::<Vec<_>>()
         )
     }
 }
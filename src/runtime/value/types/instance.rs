@@ -6,7 +6,7 @@ use crate::runtime::value::{RuntimeException, ValueRef};
 use std::collections::HashMap;
 use std::hash::Hash;
 
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Eq, Clone, Debug)]
 pub struct InstanceValue {
     pub id: usize,
     pub class: ValueRef,
@@ -24,15 +24,22 @@ impl AttributeAccessible for InstanceValue {
         }
         let class_value = extract_class_ref(&self.class);
         Ok(
-            get_class_attr(runtime, class_value, name)?.ok_or(exception::ATTRIBUTE.runtime(
+            get_class_attr(runtime, &class_value, name)?.ok_or(exception::ATTRIBUTE.runtime(
                 format!("Attribute {} not found in class {:?}.", name, self.class),
             ))?,
         )
     }
 }
 
+// instances are mutable, so they are compared and hashed by identity, not by attributes
 impl Hash for InstanceValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.id.hash(state)
     }
 }
+
+impl PartialEq for InstanceValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
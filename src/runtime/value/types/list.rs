@@ -1,9 +1,9 @@
 use crate::runtime::Runtime;
 use crate::runtime::utils::value_to_ref;
-use crate::runtime::value::exception::{ATTRIBUTE, TYPE};
+use crate::runtime::value::exception::{ATTRIBUTE, INDEX_ERROR, TYPE, VALUE};
 use crate::runtime::value::indexable::{AttributeAccessible, Subscriptable};
 use crate::runtime::value::methods::MethodProvider;
-use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use crate::runtime::value::{RuntimeException, SliceValue, Value, ValueRef};
 use std::hash::Hash;
 
 #[derive(Eq, PartialEq, Debug, Clone)]
@@ -30,15 +30,68 @@ impl Subscriptable for ListValue {
         key: &ValueRef,
     ) -> Result<ValueRef, RuntimeException> {
         let index = match &*key.borrow() {
-            Value::Int(index) => index.0 as usize,
-            _ => Err(TYPE.runtime("Lists can only be subscribed to with integers".to_string()))?,
+            Value::Int(index) => {
+                // negative indexes count from the end, as in slices
+                let length = self.elements.len() as i64;
+                let position = if index.0 < 0 {
+                    index.0 + length
+                } else {
+                    index.0
+                };
+                if !(0..length).contains(&position) {
+                    return Err(INDEX_ERROR.runtime(format!("List index {} out of range", index.0)));
+                }
+                position as usize
+            }
+            Value::Slice(slice) => {
+                let elements = slice
+                    .indices(self.elements.len())?
+                    .into_iter()
+                    .map(|i| self.elements[i].clone())
+                    .collect();
+                return Ok(value_to_ref(Value::list(elements)));
+            }
+            _ => Err(
+                TYPE.runtime("Lists can only be subscribed to with integers or slices".to_string())
+            )?,
         };
         Ok(self.elements[index].clone())
     }
 }
 
+impl ListValue {
+    pub fn assign_slice(
+        &mut self,
+        slice: &SliceValue,
+        values: Vec<ValueRef>,
+    ) -> Result<(), RuntimeException> {
+        let (start, end, step) = slice.bounds(self.elements.len())?;
+        if step == 1 {
+            // contiguous slices may grow or shrink the list
+            let (start, end) = (start as usize, end.max(start) as usize);
+            self.elements.splice(start..end, values);
+            return Ok(());
+        }
+        let indices = slice.indices(self.elements.len())?;
+        if indices.len() != values.len() {
+            return Err(VALUE.runtime(format!(
+                "Cannot assign {} values to an extended slice of size {}",
+                values.len(),
+                indices.len()
+            )));
+        }
+        for (index, value) in indices.into_iter().zip(values) {
+            self.elements[index] = value;
+        }
+        Ok(())
+    }
+}
+
+// consistent with `Eq`, but lists are mutable and never accepted as map keys (see `Value::is_hashable`)
 impl Hash for ListValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        unreachable!("Shouldn't be hashing a list value")
+        self.elements
+            .iter()
+            .for_each(|element| element.borrow().hash(state))
     }
 }
@@ -1,15 +1,15 @@
-use std::collections::HashMap;
 use crate::runtime::Runtime;
 use crate::runtime::utils::value_to_ref;
 use crate::runtime::value::exception;
 use crate::runtime::value::indexable::{AttributeAccessible, Subscriptable};
 use crate::runtime::value::methods::MethodProvider;
 use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use indexmap::IndexMap;
 use std::hash::Hash;
 
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub struct MapValue {
-    pub properties: HashMap<Value, ValueRef>,
+    pub properties: IndexMap<Value, ValueRef>, // insertion ordered
 }
 
 impl AttributeAccessible for MapValue {
@@ -32,14 +32,15 @@ impl Subscriptable for MapValue {
     ) -> Result<ValueRef, RuntimeException> {
         Ok(self
             .properties
-            .get(&*key.borrow())
+            .get(key.borrow().as_key()?)
             .ok_or(exception::KEY_ERROR.runtime(format!("Key {:?} does not exist in map", key)))?
             .clone())
     }
 }
 
+// consistent with `Eq` (order independent), but maps are mutable and never accepted as map keys
 impl Hash for MapValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        unreachable!("Shouldn't be hashing a map value")
+        self.properties.len().hash(state)
     }
 }
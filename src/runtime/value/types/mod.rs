@@ -8,5 +8,9 @@ pub mod int;
 pub mod list;
 pub mod map;
 pub mod method;
+pub mod module;
+pub mod reference;
 pub mod rust_method;
+pub mod slice;
 pub mod string;
+pub mod tuple;
@@ -0,0 +1,50 @@
+use crate::runtime::Runtime;
+use crate::runtime::value::exception;
+use crate::runtime::value::indexable::AttributeAccessible;
+use crate::runtime::value::{RuntimeException, ValueRef};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Eq, Clone, Debug)]
+pub struct ModuleValue {
+    pub id: usize,
+    pub name: String,
+    pub path: String,
+    pub code_object_id: usize, // the module's frame lives in the runtime's frames cache
+    pub variable_index_lookup: HashMap<String, usize>,
+}
+
+impl AttributeAccessible for ModuleValue {
+    fn get_attr(
+        &mut self,
+        runtime: &mut Runtime,
+        name: &String,
+    ) -> Result<ValueRef, RuntimeException> {
+        let var_index =
+            self.variable_index_lookup
+                .get(name)
+                .ok_or(exception::ATTRIBUTE.runtime(format!(
+                    "Module {} ({}) has no attribute {}.",
+                    self.name, self.path, name
+                )))?;
+        Ok(runtime
+            .frames_cache
+            .get(&self.code_object_id)
+            .unwrap()
+            .variables
+            .borrow()[*var_index]
+            .to_ref())
+    }
+}
+
+impl Hash for ModuleValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl PartialEq for ModuleValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
@@ -0,0 +1,45 @@
+use crate::runtime::value::StackValue;
+use std::cell::RefCell;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// `&name`: a variable slot of a scope, read and written through with `*reference`.
+/// The slots stay alive as long as the reference does, even after the scope returns.
+#[derive(Clone)]
+pub struct ReferenceValue {
+    pub name: String,
+    pub(crate) slots: Rc<RefCell<Vec<StackValue>>>,
+    pub index: usize,
+}
+
+impl ReferenceValue {
+    pub(crate) fn get(&self) -> StackValue {
+        self.slots.borrow()[self.index].clone()
+    }
+
+    pub(crate) fn set(&self, value: StackValue) {
+        self.slots.borrow_mut()[self.index] = value;
+    }
+}
+
+// compared by slot, the variables may hold the reference itself
+impl PartialEq for ReferenceValue {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.slots, &other.slots) && self.index == other.index
+    }
+}
+
+impl Eq for ReferenceValue {}
+
+impl Hash for ReferenceValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        Rc::as_ptr(&self.slots).hash(state);
+        self.index.hash(state);
+    }
+}
+
+impl std::fmt::Debug for ReferenceValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Reference(&{})", self.name)
+    }
+}
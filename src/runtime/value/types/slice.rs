@@ -0,0 +1,47 @@
+use crate::runtime::value::RuntimeException;
+use crate::runtime::value::exception;
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct SliceValue {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub step: Option<i64>,
+}
+
+impl SliceValue {
+    /// Resolves the slice against a sequence of `len` items into clamped `(start, end, step)`.
+    /// Negative bounds count from the end, and a negative step walks the sequence backwards.
+    pub fn bounds(&self, len: usize) -> Result<(i64, i64, i64), RuntimeException> {
+        let len = len as i64;
+        let step = self.step.unwrap_or(1);
+        if step == 0 {
+            return Err(exception::VALUE.runtime("Slice step cannot be zero".to_string()));
+        }
+        let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+        let clamp = |bound: i64| {
+            let bound = if bound < 0 { bound + len } else { bound };
+            bound.clamp(lower, upper)
+        };
+        let start = self
+            .start
+            .map(clamp)
+            .unwrap_or(if step > 0 { lower } else { upper });
+        let end = self
+            .end
+            .map(clamp)
+            .unwrap_or(if step > 0 { upper } else { lower });
+        Ok((start, end, step))
+    }
+
+    /// Indices selected by the slice, in iteration order.
+    pub fn indices(&self, len: usize) -> Result<Vec<usize>, RuntimeException> {
+        let (start, end, step) = self.bounds(len)?;
+        let mut indices = Vec::new();
+        let mut i = start;
+        while (step > 0 && i < end) || (step < 0 && i > end) {
+            indices.push(i as usize);
+            i += step;
+        }
+        Ok(indices)
+    }
+}
@@ -1,6 +1,10 @@
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
 use crate::runtime::value::binary::traits::Binary;
 use crate::runtime::value::exception;
-use crate::runtime::value::{RuntimeException, Value};
+use crate::runtime::value::indexable::{AttributeAccessible, Subscriptable};
+use crate::runtime::value::methods::MethodProvider;
+use crate::runtime::value::{RuntimeException, Value, ValueRef};
 
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
 pub struct StringValue(pub String);
@@ -66,30 +70,94 @@ impl Binary for StringValue {
     }
 
     fn greater(&mut self, other: &Value) -> Result<Value, RuntimeException> {
-        Err(exception::INVALID_OPERATION.runtime(format!(
-            "Invalid binary operation: {:?} > {:?}",
-            self, other
-        )))
+        match other {
+            Value::String(other) => Ok(Value::bool(self.0 > other.0)),
+            _ => Err(exception::INVALID_OPERATION.runtime(format!(
+                "Invalid binary operation: {:?} > {:?}",
+                self, other
+            ))),
+        }
     }
 
     fn greater_equals(&mut self, other: &Value) -> Result<Value, RuntimeException> {
-        Err(exception::INVALID_OPERATION.runtime(format!(
-            "Invalid binary operation: {:?} >= {:?}",
-            self, other
-        )))
+        match other {
+            Value::String(other) => Ok(Value::bool(self.0 >= other.0)),
+            _ => Err(exception::INVALID_OPERATION.runtime(format!(
+                "Invalid binary operation: {:?} >= {:?}",
+                self, other
+            ))),
+        }
     }
 
     fn less(&mut self, other: &Value) -> Result<Value, RuntimeException> {
-        Err(exception::INVALID_OPERATION.runtime(format!(
-            "Invalid binary operation: {:?} < {:?}",
-            self, other
-        )))
+        match other {
+            Value::String(other) => Ok(Value::bool(self.0 < other.0)),
+            _ => Err(exception::INVALID_OPERATION.runtime(format!(
+                "Invalid binary operation: {:?} < {:?}",
+                self, other
+            ))),
+        }
     }
 
     fn less_equals(&mut self, other: &Value) -> Result<Value, RuntimeException> {
-        Err(exception::INVALID_OPERATION.runtime(format!(
-            "Invalid binary operation: {:?} <= {:?}",
-            self, other
-        )))
+        match other {
+            Value::String(other) => Ok(Value::bool(self.0 <= other.0)),
+            _ => Err(exception::INVALID_OPERATION.runtime(format!(
+                "Invalid binary operation: {:?} <= {:?}",
+                self, other
+            ))),
+        }
+    }
+}
+
+impl AttributeAccessible for StringValue {
+    fn get_attr(
+        &mut self,
+        _runtime: &mut Runtime,
+        name: &String,
+    ) -> Result<ValueRef, RuntimeException> {
+        let func = StringValue::get_method(name.as_str())
+            .ok_or(exception::ATTRIBUTE.runtime(format!("Attribute not found: {}", name)))?;
+        Ok(value_to_ref(Value::rust_method(func, None)))
+    }
+}
+
+impl Subscriptable for StringValue {
+    fn index(
+        &mut self,
+        _runtime: &mut Runtime,
+        key: &ValueRef,
+    ) -> Result<ValueRef, RuntimeException> {
+        match &*key.borrow() {
+            Value::Int(index) => {
+                // indexes count chars, negative indexes count from the end
+                let length = self.0.chars().count() as i64;
+                let position = if index.0 < 0 {
+                    index.0 + length
+                } else {
+                    index.0
+                };
+                let char = (0..length)
+                    .contains(&position)
+                    .then(|| self.0.chars().nth(position as usize))
+                    .flatten()
+                    .ok_or(exception::INDEX_ERROR.runtime(format!(
+                        "String index {} is out of bounds for length {}",
+                        index.0, length
+                    )))?;
+                Ok(value_to_ref(Value::string(char.to_string())))
+            }
+            Value::Slice(slice) => {
+                let chars: Vec<char> = self.0.chars().collect();
+                let sliced = slice
+                    .indices(chars.len())?
+                    .into_iter()
+                    .map(|i| chars[i])
+                    .collect();
+                Ok(value_to_ref(Value::string(sliced)))
+            }
+            _ => Err(exception::TYPE
+                .runtime("Strings can only be subscribed to with integers or slices".to_string())),
+        }
     }
 }
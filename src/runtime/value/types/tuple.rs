@@ -0,0 +1,69 @@
+use crate::runtime::Runtime;
+use crate::runtime::utils::value_to_ref;
+use crate::runtime::value::exception;
+use crate::runtime::value::indexable::{AttributeAccessible, Subscriptable};
+use crate::runtime::value::methods::MethodProvider;
+use crate::runtime::value::{RuntimeException, Value, ValueRef};
+use std::hash::Hash;
+
+/// Immutable sequence; hashable (usable as a map key) when all of its elements are.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct TupleValue {
+    pub elements: Vec<ValueRef>,
+}
+
+impl AttributeAccessible for TupleValue {
+    fn get_attr(
+        &mut self,
+        _runtime: &mut Runtime,
+        name: &String,
+    ) -> Result<ValueRef, RuntimeException> {
+        let func = TupleValue::get_method(name.as_str())
+            .ok_or(exception::ATTRIBUTE.runtime(format!("Attribute not found: {}", name)))?;
+        Ok(value_to_ref(Value::rust_method(func, None)))
+    }
+}
+
+impl Subscriptable for TupleValue {
+    fn index(
+        &mut self,
+        _runtime: &mut Runtime,
+        key: &ValueRef,
+    ) -> Result<ValueRef, RuntimeException> {
+        match &*key.borrow() {
+            Value::Int(index) => {
+                let length = self.elements.len() as i64;
+                let position = if index.0 < 0 {
+                    index.0 + length
+                } else {
+                    index.0
+                };
+                if !(0..length).contains(&position) {
+                    return Err(exception::INDEX_ERROR.runtime(format!(
+                        "Tuple index {} is out of bounds for length {}",
+                        index.0, length
+                    )));
+                }
+                Ok(self.elements[position as usize].clone())
+            }
+            Value::Slice(slice) => {
+                let elements = slice
+                    .indices(self.elements.len())?
+                    .into_iter()
+                    .map(|i| self.elements[i].clone())
+                    .collect();
+                Ok(value_to_ref(Value::tuple(elements)))
+            }
+            _ => Err(exception::TYPE
+                .runtime("Tuples can only be subscribed to with integers or slices".to_string())),
+        }
+    }
+}
+
+impl Hash for TupleValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.elements
+            .iter()
+            .for_each(|element| element.borrow().hash(state))
+    }
+}
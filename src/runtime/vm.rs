@@ -1,8 +1,9 @@
+use crate::compiler::ByteOp;
 use crate::compiler::code_object::CodeObject;
 use crate::runtime::Runtime;
-use crate::runtime::utils::extract_int_ref;
-use crate::runtime::value::{RuntimeException, Value};
-use std::cell::RefCell;
+use crate::runtime::frame::RuntimeFrame;
+use crate::runtime::value::exception;
+use crate::runtime::value::{ReferenceValue, RuntimeException, StackValue, Value, ValueRef};
 use std::rc::Rc;
 
 pub(crate) fn load_constant(
@@ -10,17 +11,36 @@ pub(crate) fn load_constant(
     code_object: &CodeObject,
     constant_index: usize,
 ) -> Result<(), RuntimeException> {
-    let constant_value = code_object.constants[constant_index].clone();
+    let constant_value = &code_object.constants[constant_index];
+    // mutable constants are copied, otherwise every evaluation would share one container
+    let constant_value = match &*constant_value.borrow() {
+        Value::List(_) | Value::Map(_) => StackValue::from(constant_value.borrow().clone()),
+        _ => StackValue::from(constant_value.clone()),
+    };
     runtime.mem_stack.push(constant_value);
     Ok(())
 }
 
+pub(crate) fn load_null(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    runtime.mem_stack.push(StackValue::Null);
+    Ok(())
+}
+
+pub(crate) fn load_builtin(
+    runtime: &mut Runtime,
+    builtin_index: usize,
+) -> Result<(), RuntimeException> {
+    let builtin = runtime.builtins[builtin_index].clone();
+    runtime.mem_stack.push(StackValue::Object(builtin));
+    Ok(())
+}
+
 pub(crate) fn load_local(
     runtime: &mut Runtime,
     variable_index: usize,
 ) -> Result<(), RuntimeException> {
     let frame = runtime.frames_stack.last().unwrap();
-    let var_value = frame.variables[variable_index].clone();
+    let var_value = frame.variables.borrow()[variable_index].clone();
     runtime.mem_stack.push(var_value);
     Ok(())
 }
@@ -29,42 +49,129 @@ pub(crate) fn load_nonlocal(
     runtime: &mut Runtime,
     nonlocal_index: usize,
 ) -> Result<(), RuntimeException> {
-    let frame_index = extract_int_ref(&runtime.mem_stack.pop().unwrap()) as usize;
-    let nonlocal_value =
-        runtime.frames_stack.get(frame_index).unwrap().variables[nonlocal_index].clone();
+    let code_object_id = pop_scope(runtime);
+    let scope_frame = scope_frame(runtime, code_object_id);
+    let nonlocal_value = scope_frame.variables.borrow()[nonlocal_index].clone();
     runtime.mem_stack.push(nonlocal_value);
     Ok(())
 }
 
+fn scope_frame(runtime: &Runtime, code_object_id: usize) -> &RuntimeFrame {
+    // scopes still executing live on the frame stack, finished module scopes in the frames cache
+    match runtime
+        .frames_stack_id_lookup
+        .get(&code_object_id)
+        .and_then(|frame_indices| frame_indices.last())
+    {
+        Some(frame_index) => runtime.frames_stack.get(*frame_index).unwrap(),
+        None => runtime.frames_cache.get(&code_object_id).unwrap(),
+    }
+}
+
+pub(crate) fn load_reference(
+    runtime: &mut Runtime,
+    code_object: &CodeObject,
+    variable_index: usize,
+) -> Result<(), RuntimeException> {
+    let frame = runtime.frames_stack.last().unwrap();
+    let reference = Value::reference(
+        code_object.variables[variable_index].clone(),
+        frame.variables.clone(),
+        variable_index,
+    );
+    // a slot holding a reference to itself is a cycle
+    let slots = frame.variables.clone();
+    runtime.gc.track_slots(&slots);
+    runtime.mem_stack.push(StackValue::from(reference));
+    Ok(())
+}
+
+pub(crate) fn load_nonlocal_reference(
+    runtime: &mut Runtime,
+    nonlocal_index: usize,
+) -> Result<(), RuntimeException> {
+    let code_object_id = pop_scope(runtime);
+    let scope_frame = scope_frame(runtime, code_object_id);
+    let name = scope_frame
+        .variable_index_lookup
+        .iter()
+        .find_map(|(name, index)| (*index == nonlocal_index).then(|| name.clone()))
+        .unwrap_or_default();
+    let slots = scope_frame.variables.clone();
+    runtime.gc.track_slots(&slots);
+    let reference = Value::reference(name, slots, nonlocal_index);
+    runtime.mem_stack.push(StackValue::from(reference));
+    Ok(())
+}
+
+pub(crate) fn deref(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let reference = runtime.mem_stack.pop().unwrap().into_ref();
+    let value = extract_reference(&reference)?.get();
+    runtime.mem_stack.push(value);
+    Ok(())
+}
+
+pub(crate) fn store_deref(runtime: &mut Runtime) -> Result<(), RuntimeException> {
+    let reference = runtime.mem_stack.pop().unwrap().into_ref();
+    let value = runtime.mem_stack.pop().unwrap();
+    extract_reference(&reference)?.set(value);
+    Ok(())
+}
+
+fn extract_reference(value: &ValueRef) -> Result<ReferenceValue, RuntimeException> {
+    match &*value.borrow() {
+        Value::Reference(reference) => Ok(reference.clone()),
+        other => Err(exception::TYPE.runtime(format!(
+            "Cannot dereference a '{}' value",
+            other.type_name()
+        ))),
+    }
+}
+
 pub(crate) fn load_scope(
     runtime: &mut Runtime,
     code_object_id: usize,
 ) -> Result<(), RuntimeException> {
-    let scope_frame_index = runtime
-        .frames_stack_id_lookup
-        .get(&code_object_id)
-        .unwrap()
-        .last()
-        .unwrap();
-    let index_value = Value::int(*scope_frame_index as i64);
-    runtime.mem_stack.push(Rc::new(RefCell::new(index_value)));
+    runtime
+        .mem_stack
+        .push(StackValue::Int(code_object_id as i64));
     Ok(())
 }
 
+fn pop_scope(runtime: &mut Runtime) -> usize {
+    match runtime.mem_stack.pop().unwrap() {
+        StackValue::Int(code_object_id) => code_object_id as usize,
+        other => unreachable!("Expected a scope pushed by LoadScope, got {:?}", other),
+    }
+}
+
 pub(crate) fn pop_check_truthy(runtime: &mut Runtime) -> bool {
-    let condition = runtime.mem_stack.pop().unwrap();
-    (&*condition.borrow()).is_truthy()
+    runtime.mem_stack.pop().unwrap().is_truthy()
 }
 
-pub(crate) fn apply_bin_op<F>(runtime: &mut Runtime, f: F) -> Result<(), RuntimeException>
+pub(crate) fn apply_bin_op<F>(
+    runtime: &mut Runtime,
+    operation: ByteOp,
+    f: F,
+) -> Result<(), RuntimeException>
 where
     F: Fn(&mut Value, &Value) -> Result<Value, RuntimeException>,
 {
     let b = runtime.mem_stack.pop().unwrap();
     let a = runtime.mem_stack.pop().unwrap();
-    runtime.mem_stack.push(Rc::new(RefCell::new(f(
-        &mut *a.borrow_mut(),
-        &*b.borrow(),
-    )?)));
+    if let Some(result) = StackValue::arithmetic(operation, &a, &b) {
+        runtime.mem_stack.push(result);
+        return Ok(());
+    }
+    let result = match (&a, &b) {
+        // both operands may be the same shared value, as in `x + x`
+        (StackValue::Object(a), StackValue::Object(b)) if Rc::ptr_eq(a, b) => {
+            f(&mut a.borrow().clone(), &b.borrow())?
+        }
+        (StackValue::Object(a), _) => b.with_value(|b| f(&mut a.borrow_mut(), b))?,
+        _ => b.with_value(|b| f(&mut a.to_value(), b))?,
+    };
+    // primitive results stay on the stack without an allocation
+    runtime.mem_stack.push(StackValue::from(result));
     Ok(())
 }
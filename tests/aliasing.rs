@@ -0,0 +1,211 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn assignment_shares_lists() {
+    assert_last_true(
+        "a = [1, 2];
+        b = a
+        b.push(3)
+        shared = a == [1, 2, 3]",
+    );
+}
+
+#[test]
+fn assignment_shares_maps() {
+    assert_last_true(
+        "a = {\"k\": 1};
+        b = a
+        b[\"j\"] = 2
+        shared = a.contains(\"j\")",
+    );
+}
+
+#[test]
+fn assignment_shares_instances() {
+    assert_last_true(
+        "class Box { init(self, v) { self.v = v } }
+        a = Box(1)
+        b = a
+        b.v = 2
+        shared = a.v == 2",
+    );
+}
+
+#[test]
+fn rebinding_does_not_affect_other_names() {
+    assert_last_true(
+        "a = [1];
+        b = a
+        b = [2];
+        unchanged = a == [1]",
+    );
+}
+
+#[test]
+fn primitives_behave_as_values() {
+    assert_last_true(
+        "x = 1
+        y = x
+        y += 1
+        s = \"a\"
+        t = s
+        t += \"b\"
+        unchanged = x == 1 && s == \"a\"",
+    );
+}
+
+#[test]
+fn arguments_share_compound_values() {
+    assert_last_true(
+        "fn append(l) { l.push(4) }
+        a = [1];
+        append(a)
+        shared = a == [1, 4]",
+    );
+}
+
+#[test]
+fn parameter_rebinding_stays_local() {
+    assert_last_true(
+        "fn rebind(l, n) {
+            l = [0];
+            n = 10
+        }
+        a = [1];
+        x = 1
+        rebind(a, x)
+        unchanged = a == [1] && x == 1",
+    );
+}
+
+#[test]
+fn containers_share_their_elements() {
+    assert_last_true(
+        "inner = [1];
+        outer = [inner, inner]
+        inner.push(2)
+        shared = outer[0] == [1, 2] && outer[1] == [1, 2]",
+    );
+}
+
+#[test]
+fn subscript_assignment_is_visible_through_aliases() {
+    assert_last_true(
+        "a = [1, 2];
+        b = a
+        b[0] = 9
+        shared = a == [9, 2]",
+    );
+}
+
+#[test]
+fn literals_create_a_new_container_each_evaluation() {
+    assert_last_true(
+        "fn make() { return [1] }
+        first = make()
+        first.push(2)
+        second = make()
+        fresh = second == [1]",
+    );
+}
+
+#[test]
+fn copy_is_shallow() {
+    assert_last_true(
+        "nested = [[1], [2]];
+        copied = copy(nested)
+        copied.push([3])
+        copied[0].push(9)
+        shallow = nested.len() == 2 && nested[0] == [1, 9]",
+    );
+}
+
+#[test]
+fn copy_of_instance_has_its_own_attributes() {
+    assert_last_true(
+        "class Box { init(self, v) { self.v = v } }
+        a = Box(1)
+        b = copy(a)
+        b.v = 2
+        independent = a.v == 1",
+    );
+}
+
+#[test]
+fn deepcopy_copies_nested_containers() {
+    assert_last_true(
+        "nested = {\"items\": [[1]]};
+        copied = deepcopy(nested)
+        copied[\"items\"][0].push(2)
+        independent = nested[\"items\"][0] == [1]",
+    );
+}
+
+#[test]
+fn deepcopy_preserves_sharing_within_the_copy() {
+    assert_last_true(
+        "inner = [1];
+        outer = [inner, inner]
+        copied = deepcopy(outer)
+        copied[0].push(2)
+        shared = copied[1] == [1, 2] && inner == [1]",
+    );
+}
+
+#[test]
+fn bound_methods_keep_their_instance() {
+    assert_last_true(
+        "class Box {
+            init(self, v) { self.v = v }
+            fn get(self) { return self.v }
+        }
+        first = Box(1).get
+        second = Box(2).get
+        bound = first() == 1 && second() == 2",
+    );
+}
+
+#[test]
+fn tuples_share_their_mutable_elements() {
+    assert_last_true(
+        "inner = [1]
+        t = (inner, 2)
+        u = t
+        inner.push(2)
+        shared = u[0] == [1, 2]",
+    );
+}
+
+#[test]
+fn copy_of_map_is_shallow() {
+    assert_last_true(
+        "m = {\"k\": [1]}
+        c = copy(m)
+        c[\"k\"].push(2)
+        c[\"j\"] = 1
+        shallow = m[\"k\"] == [1, 2] && m.contains(\"j\") == false",
+    );
+}
+
+#[test]
+fn deepcopy_of_instance_copies_its_attributes() {
+    assert_last_true(
+        "class Box { init(self, v) { self.v = v } }
+        b = Box([1])
+        d = deepcopy(b)
+        d.v.push(2)
+        independent = b.v == [1] && d.v == [1, 2]",
+    );
+}
+
+#[test]
+fn copies_of_primitives_are_equal_values() {
+    assert_last_true(
+        "s = \"ab\"
+        s2 = s
+        s2 = s2 + \"c\"
+        same = copy(5) == 5 && deepcopy(s) == \"ab\" && s == \"ab\"",
+    );
+}
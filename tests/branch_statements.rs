@@ -0,0 +1,101 @@
+mod common;
+
+use common::{assert_last_true, run_script, run_script_with_args, variables};
+
+/// The dumped operations of the script's function taking `x`, compiled without optimization
+/// so no pushed value is dropped along with its pop.
+fn function_operations(source: &str) -> String {
+    run_script_with_args(source, &["--opt-level", "0"])
+        .lines()
+        .find_map(|line| line.strip_prefix("var Function(<x>"))
+        .map(String::from)
+        .expect("function not dumped")
+}
+
+/// The values the script left on the stack when it finished.
+fn leftover_values(source: &str) -> Vec<String> {
+    run_script(source)
+        .lines()
+        .filter_map(|line| line.strip_prefix("mem "))
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn loop_bodies_pop_the_values_they_leave() {
+    let operations = function_operations("fn f(x) { while x { x = false; 5 } }");
+    assert!(
+        operations.contains("[LoadConstant, 1], [Pop, 0], [Jump, 0]"),
+        "{}",
+        operations
+    );
+    assert!(leftover_values("i = 0\nwhile i < 3 { i = i + 1; 8 }").is_empty());
+}
+
+#[test]
+fn if_statements_with_a_value_fill_in_the_missing_branch() {
+    let operations = function_operations("fn f(x) { if x { 1 }; 2 }");
+    assert!(operations.contains("[LoadNull, 0]"), "{}", operations);
+}
+
+#[test]
+fn match_arms_pop_all_but_their_last_value() {
+    let operations = function_operations("fn f(x) { match x { 1 => { 3; 4 }, _ => 5 }; 6 }");
+    assert!(
+        operations.contains("[LoadConstant, 1], [Pop, 0], [LoadConstant, 2]"),
+        "{}",
+        operations
+    );
+}
+
+#[test]
+fn if_statements_in_loops_leave_nothing_behind() {
+    assert!(
+        leftover_values(
+            "i = 0
+while i < 3 {
+    i = i + 1
+    if i == 2 { 5; 6 } else { 7 }
+    if i == 1 { 8 }
+    match i { 3 => { 9; 10 }, _ => 11 }
+}"
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn call_statements_in_loops_are_popped_once() {
+    assert_last_true(
+        "fn one() { return 1 }
+fn five() {
+    i = 0
+    while i < 2 {
+        i = i + 1
+        one()
+    }
+    return 5
+}
+ok = 7 + five() == 12",
+    );
+}
+
+#[test]
+fn branch_statements_keep_their_results() {
+    let variables = variables(
+        "fn pick(x) {
+    if x { 1 }
+    match x { true => 2, _ => { 3; 4 } }
+    if x { 5; 6 } else { 7 }
+}
+i = 0
+while i < 3 { i = i + 1; 8 }
+picked = [pick(true), pick(false)]",
+    );
+    assert_eq!(variables[1], "Int(IntValue(3))");
+    assert!(
+        variables[2].contains("IntValue(6)") && variables[2].contains("IntValue(7)"),
+        "{}",
+        variables[2]
+    );
+}
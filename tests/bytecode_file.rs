@@ -0,0 +1,149 @@
+mod common;
+
+use common::write_script;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const PROGRAM: &str = "fn offset(start) {
+    count = start
+    fn step(by) { return count + by }
+    return step(5)
+}
+class Shape {
+    init(self, sides) { self.sides = sides }
+    fn describe(self) {
+        return match self.sides {
+            3 => \"triangle\",
+            4 => \"square\",
+            _ => \"polygon\",
+        }
+    }
+}
+stepped = offset(10)
+names = [Shape(3).describe(), Shape(4).describe(), Shape(7).describe()]
+constants = [[1, 2.5, \"three\"], {\"key\": true}]
+total = 0
+i = 0
+while i < 5 { i = i + 1; total = total + i }";
+
+fn interpreter(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .args(args)
+        .output()
+        .expect("failed to run the interpreter")
+}
+
+/// Compiles `source` to a `.shrc` file next to its script and returns the file's path.
+fn compile(source: &str) -> PathBuf {
+    let script = write_script(source);
+    let compiled = script.with_extension("shrc");
+    let output = interpreter(&[Path::new("compile"), &script]);
+    std::fs::remove_file(&script).ok();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    compiled
+}
+
+/// The dumped values the program ends with, leaving out functions and classes, which carry
+/// their code objects.
+fn values(output: &Output) -> Vec<String> {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("var "))
+        .filter(|value| !value.starts_with("Function(") && !value.starts_with("Class("))
+        .map(String::from)
+        .collect()
+}
+
+/// Runs `bytes` as a compiled file and returns what the interpreter reported on failure.
+fn load_error(bytes: &[u8]) -> String {
+    let path = write_script("").with_extension("shrc");
+    std::fs::write(&path, bytes).unwrap();
+    let output = interpreter(&[&path]);
+    std::fs::remove_file(&path).ok();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    stderr
+}
+
+#[test]
+fn compiled_scripts_run_like_their_source() {
+    let script = write_script(PROGRAM);
+    let from_source = values(&interpreter(&[&script]));
+    std::fs::remove_file(&script).ok();
+    let compiled = compile(PROGRAM);
+    let from_compiled = values(&interpreter(&[&compiled]));
+    std::fs::remove_file(&compiled).ok();
+    assert!(!from_source.is_empty());
+    assert_eq!(from_compiled, from_source);
+}
+
+#[test]
+fn compiling_the_same_source_writes_the_same_file() {
+    let script = write_script(PROGRAM);
+    let outputs = [
+        script.with_extension("first"),
+        script.with_extension("second"),
+    ];
+    let compiled: Vec<Vec<u8>> = outputs
+        .iter()
+        .map(|output| {
+            let result = interpreter(&[Path::new("compile"), &script, Path::new("-o"), output]);
+            assert!(result.status.success());
+            let bytes = std::fs::read(output).unwrap();
+            std::fs::remove_file(output).ok();
+            bytes
+        })
+        .collect();
+    std::fs::remove_file(&script).ok();
+    assert_eq!(compiled[0], compiled[1]);
+}
+
+#[test]
+fn files_without_the_header_are_rejected() {
+    assert!(load_error(b"i = 1").contains("not a compiled script"));
+}
+
+#[test]
+fn files_of_other_format_versions_are_rejected() {
+    let compiled = compile(PROGRAM);
+    let mut bytes = std::fs::read(&compiled).unwrap();
+    std::fs::remove_file(&compiled).ok();
+    bytes[4] = bytes[4].wrapping_add(1);
+    assert!(load_error(&bytes).contains("format version"));
+}
+
+#[test]
+fn truncated_and_padded_files_are_rejected() {
+    let compiled = compile(PROGRAM);
+    let mut bytes = std::fs::read(&compiled).unwrap();
+    std::fs::remove_file(&compiled).ok();
+    for len in (0..bytes.len()).step_by(7) {
+        assert!(load_error(&bytes[..len]).contains("Cannot load"));
+    }
+    bytes.push(0);
+    assert!(load_error(&bytes).contains("trailing bytes"));
+}
+
+#[test]
+fn parameters_missing_from_the_body_are_rejected() {
+    let compiled = compile("fn f(param) { return param }\nx = f(1)");
+    let mut bytes = std::fs::read(&compiled).unwrap();
+    std::fs::remove_file(&compiled).ok();
+    // the parameter names are written before the body's own variables
+    let at = bytes
+        .windows(6)
+        .position(|window| window == b"\x05param")
+        .unwrap();
+    bytes[at + 1..at + 6].copy_from_slice(b"other");
+    assert!(load_error(&bytes).contains("parameter other is not a variable of f"));
+}
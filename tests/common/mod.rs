@@ -0,0 +1,95 @@
+// each test crate uses its own subset of the helpers
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SCRIPT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs `source` through the interpreter binary and returns its standard output.
+pub fn run_script(source: &str) -> String {
+    run_script_with_args(source, &[])
+}
+
+/// Like `run_script`, passing `args` to the interpreter after the script's path.
+pub fn run_script_with_args(source: &str, args: &[&str]) -> String {
+    let script_path = write_script(source);
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(&script_path)
+        .args(args)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_file(&script_path).ok();
+    assert!(
+        output.status.success(),
+        "interpreter failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Runs `source`, which must end in an uncaught exception, and returns the traceback the
+/// interpreter wrote to standard error.
+pub fn run_failing_script(source: &str) -> String {
+    let script_path = write_script(source);
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(&script_path)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_file(&script_path).ok();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(
+        stderr.starts_with("Traceback (most recent call last)"),
+        "{}",
+        stderr
+    );
+    stderr
+}
+
+/// Runs `source`, which must be rejected as a syntax error before it runs, and returns the
+/// message the interpreter wrote to standard error.
+pub fn run_invalid_script(source: &str) -> String {
+    let script_path = write_script(source);
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(&script_path)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_file(&script_path).ok();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert!(!output.status.success(), "{}", stderr);
+    assert!(stderr.contains("Syntax error on line "), "{}", stderr);
+    stderr
+}
+
+/// Final values of the script's variables, in declaration order, as dumped after the run.
+pub fn variables(source: &str) -> Vec<String> {
+    run_script(source)
+        .lines()
+        .filter_map(|line| line.strip_prefix("var "))
+        .map(String::from)
+        .collect()
+}
+
+/// Asserts that the last variable the script declares ends up `true`.
+pub fn assert_last_true(source: &str) {
+    let variables = variables(source);
+    assert_eq!(
+        variables.last().map(String::as_str),
+        Some("Bool(BoolValue(true))"),
+        "script:\n{}",
+        source
+    );
+}
+
+/// Writes `source` to a fresh temporary script file.
+pub fn write_script(source: &str) -> PathBuf {
+    let script_path = std::env::temp_dir().join(format!(
+        "shriky-test-{}-{}.pik",
+        std::process::id(),
+        SCRIPT_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&script_path, source).expect("failed to write the test script");
+    script_path
+}
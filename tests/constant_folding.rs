@@ -0,0 +1,60 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script, run_script};
+
+/// The operations of the script's top level, as listed in the dump after the run.
+fn bytecode(source: &str) -> Vec<String> {
+    let output = run_script(source);
+    output
+        .lines()
+        .skip_while(|line| *line != "bytecode:")
+        .skip(1)
+        .take_while(|line| !line.starts_with("hex:"))
+        .map(|line| line.split_once(": ").map_or(line, |(_, op)| op).to_string())
+        .collect()
+}
+
+#[test]
+fn literal_arithmetic_compiles_to_one_constant() {
+    assert_eq!(
+        bytecode("w = 1 + (5/2) + (55*5) / 5 / (2**2)"),
+        ["[LoadConstant, 0]", "[PreAssign, 0]"]
+    );
+}
+
+#[test]
+fn folded_results_match_the_runtime() {
+    assert_last_true(
+        "one = 1
+        two = 2
+        a = \"a\"
+        folded = [1 + 5 / 2, -7 % 3, 2 ** -1, 0.1 + 0.2, \"a\" + \"b\", 3 > 2.5, 1 == 1.0, 1 and 0, null or 2]
+        computed = [one + 5 / two, -7 % (one + two), two ** -1, 0.1 + two / 10, a + \"b\", 3 > two + 0.5, one == 1.0, one and 0, null or two]
+        same = folded == computed",
+    );
+}
+
+#[test]
+fn failing_operations_still_raise_at_runtime() {
+    let output = run_failing_script(
+        "fine = 1 + 1
+        broken = 1 / 0",
+    );
+    assert!(output.contains("line 2, in <module>"), "{}", output);
+    assert!(output.contains("Divide by zero"), "{}", output);
+}
+
+#[test]
+fn operations_on_names_are_not_folded() {
+    assert_eq!(
+        bytecode("x = 2\ny = x * 3"),
+        [
+            "[LoadConstant, 0]",
+            "[PreAssign, 0]",
+            "[LoadLocal, 0]",
+            "[LoadConstant, 1]",
+            "[Mul, 0]",
+            "[PreAssign, 1]"
+        ]
+    );
+}
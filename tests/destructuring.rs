@@ -0,0 +1,101 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script, run_invalid_script};
+
+#[test]
+fn tuple_literals() {
+    assert_last_true(
+        "t = (1, 2)
+        single = (5,)
+        empty = ()
+        ok = t[0] == 1 and t[-1] == 2 and t == (1, 2) and single[0] == 5 and empty == ()",
+    );
+}
+
+#[test]
+fn sequences_unpack_into_names() {
+    assert_last_true(
+        "a, b = (1, 2)
+        [c, [d, e]] = [3, [4, 5]]
+        ok = a == 1 and b == 2 and c == 3 and d == 4 and e == 5",
+    );
+}
+
+#[test]
+fn swapping_through_a_tuple() {
+    assert_last_true(
+        "a = 1
+        b = 2
+        a, b = b, a
+        ok = a == 2 and b == 1",
+    );
+}
+
+#[test]
+fn maps_unpack_by_key() {
+    assert_last_true(
+        "{\"id\": id, \"tags\": [first, second]} = {\"id\": 6, \"tags\": [\"x\", \"y\"]}
+        ok = id == 6 and first == \"x\" and second == \"y\"",
+    );
+}
+
+#[test]
+fn mismatched_counts_raise_value_error() {
+    let traceback = run_failing_script("a, b = [1, 2, 3]");
+    assert!(
+        traceback.contains("ValueError: Expected 2 values to unpack, got 3"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn bracketed_target_on_the_line_after_a_list() {
+    assert_last_true(
+        "a = [1, 2]
+        [x, y] = a
+        ok = x == 1 and y == 2",
+    );
+}
+
+#[test]
+fn parenthesized_statement_on_the_line_after_a_name() {
+    assert_last_true(
+        "l = [1]
+        rl = &l
+        (*rl).push(2)
+        ok = l == [1, 2]",
+    );
+}
+
+#[test]
+fn postfix_access_still_continues_on_the_same_line() {
+    assert_last_true(
+        "word = \"a b\".split(\" \")[1]
+        second = [1,
+            2][1]
+        ok = word == \"b\" and second == 2",
+    );
+}
+
+#[test]
+fn assigned_values_can_be_unparenthesized_tuples() {
+    assert_last_true(
+        "t = 1, 2
+        a, b = t
+        u = b, a + 2
+        ok = t == (1, 2) and u == (2, 3) and a == 1 and b == 2",
+    );
+}
+
+#[test]
+fn calls_are_not_assignment_targets() {
+    let error = run_invalid_script("fn f(a) { return a }\nf(1), b = 1, 2");
+    assert!(
+        error.contains("Syntax error on line 2: Cannot assign to a call"),
+        "{}",
+        error
+    );
+    let error = run_invalid_script("fn f(a) { return a }\nf(1) = 2");
+    assert!(error.contains("Cannot assign to a call"), "{}", error);
+}
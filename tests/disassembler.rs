@@ -0,0 +1,136 @@
+mod common;
+
+use common::{run_script, write_script};
+use std::process::Command;
+
+const PROGRAM: &str = "fn outer(start) {
+    count = start
+    fn step(by) { return count + by }
+    return step(5)
+}
+i = 0
+while i < 3 { i = i + 1 }
+big = outer(i) >= 2";
+
+/// The disassembly `shriky dis` prints for `source`.
+fn dis(source: &str) -> String {
+    let script = write_script(source);
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg("dis")
+        .arg(&script)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_file(&script).ok();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// The operation lines of the disassembly, with the offset and line columns left out.
+fn operations(disassembly: &str) -> Vec<String> {
+    disassembly
+        .lines()
+        .map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            match columns
+                .iter()
+                .position(|column| column.parse::<usize>().is_err())
+            {
+                Some(name) if !line.ends_with(':') => columns[name..].join(" "),
+                _ => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn nested_code_objects_are_listed_after_their_parent() {
+    let headers: Vec<String> = dis(PROGRAM)
+        .lines()
+        .filter(|line| line.starts_with("Disassembly of "))
+        .map(|line| line.split(" (").next().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        headers,
+        [
+            "Disassembly of <module>",
+            "Disassembly of outer",
+            "Disassembly of step",
+        ]
+    );
+}
+
+#[test]
+fn operands_are_resolved() {
+    let operations = operations(&dis(PROGRAM));
+    for expected in [
+        "LoadConstant 0 (<function outer>)",
+        "PreAssign 0 (outer)",
+        "LoadLocal 1 (i)",
+        "Compare 2 (Less)",
+        "Compare 5 (GreaterEqual)",
+        "LoadNonlocal 1 (count)",
+        "LoadLocal 0 (by)",
+    ] {
+        assert!(
+            operations.iter().any(|line| line.starts_with(expected)),
+            "no {:?} in {:#?}",
+            expected,
+            operations
+        );
+    }
+    assert!(
+        operations
+            .iter()
+            .any(|line| line.ends_with("(outer)") && line.starts_with("LoadScope"))
+    );
+}
+
+#[test]
+fn jumps_name_the_labels_they_target() {
+    let operations = operations(&dis(PROGRAM));
+    let position = |line: &str| operations.iter().position(|op| op == line).unwrap();
+    let condition = operations
+        .iter()
+        .find(|line| line.starts_with("PopJumpIfFalse"))
+        .unwrap();
+    let back = operations
+        .iter()
+        .find(|line| line.starts_with("Jump"))
+        .unwrap();
+    assert!(condition.ends_with("(to L1)"), "{}", condition);
+    assert!(back.ends_with("(to L0)"), "{}", back);
+    // the loop starts at the condition and ends after the jump back
+    assert!(position("L0:") < position(condition));
+    assert_eq!(position("L1:"), position(back) + 1);
+}
+
+#[test]
+fn dis_builtin_prints_a_function() {
+    let output = run_script(
+        "fn outer(start) {
+    count = start
+    fn step(by) { return count + by }
+    dis(step)
+    return step(5)
+}
+result = outer(1)",
+    );
+    let operations = operations(&output);
+    assert!(operations[0].starts_with("Disassembly of step ("));
+    // the enclosing function is running, so its scope has a name
+    assert!(
+        operations
+            .iter()
+            .any(|line| line.starts_with("LoadScope") && line.ends_with("(outer)"))
+    );
+    assert!(
+        operations
+            .iter()
+            .any(|line| line == "LoadNonlocal 1 (count)")
+    );
+    assert!(!output.contains("Disassembly of outer"));
+}
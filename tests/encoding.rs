@@ -0,0 +1,46 @@
+mod common;
+
+use common::{assert_last_true, run_script};
+
+/// The encoded top-level code of the script, as dumped after the run.
+fn hex(source: &str) -> String {
+    let output = run_script(source);
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("hex: "))
+        .map(|hex| hex.trim_matches('"').to_string())
+        .expect("no hex dump")
+}
+
+#[test]
+fn operations_without_operands_take_one_byte() {
+    // LoadConstant 0, PreAssign 0, LoadLocal 0, LoadConstant 1, Add, PreAssign 1
+    assert_eq!(
+        hex("a = 1\nb = a + 2"),
+        "0x00 0x00 0x27 0x00 0x01 0x00 0x00 0x01 0x15 0x27 0x01"
+    );
+}
+
+#[test]
+fn large_operands_take_more_bytes() {
+    let mut source: String = (0..200).map(|i| format!("v{} = {}\n", i, i)).collect();
+    source.push_str("last = v199 == 199");
+    // the 200th variable's index, 199, is two seven-bit groups
+    assert!(hex(&source).contains("0x27 0xC7 0x01"));
+    assert_last_true(&source);
+}
+
+#[test]
+fn jumps_over_long_bodies_land_on_their_targets() {
+    let body: String = (0..40).map(|_| "total = total + 1\n").collect();
+    assert_last_true(&format!(
+        "total = 0
+        i = 0
+        while i < 3 {{
+            {}
+            i = i + 1
+        }}
+        counted = total == 120",
+        body
+    ));
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn collects_self_referencing_list() {
+    assert_last_true(
+        "a = [1]
+        a.push(a)
+        a = null
+        collected = gc() > 0",
+    );
+}
+
+#[test]
+fn collects_instance_storing_itself() {
+    assert_last_true(
+        "class Node { init(self) { self.me = self } }
+        n = Node()
+        n = null
+        collected = gc() == 1",
+    );
+}
+
+#[test]
+fn keeps_reachable_cycles() {
+    assert_last_true(
+        "a = [1]
+        a.push(a)
+        nothing = gc() == 0
+        intact = a[1][1][0] == 1
+        a = null
+        kept = nothing and intact",
+    );
+}
+
+#[test]
+fn collects_frames_referencing_themselves() {
+    assert_last_true(
+        "fn f() { r = &r }
+        f()
+        collected = gc() == 2",
+    );
+}
+
+#[test]
+fn stats_count_collections() {
+    assert_last_true(
+        "m = {}
+        m[\"self\"] = m
+        m = null
+        gc()
+        stats = gc_stats()
+        counted = stats[\"collections\"] == 1 and stats[\"collected\"] == 1",
+    );
+}
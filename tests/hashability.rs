@@ -0,0 +1,71 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+fn assert_unhashable(source: &str, type_name: &str) {
+    let traceback = run_failing_script(source);
+    assert!(
+        traceback.contains(&format!(
+            "TypeError: Unhashable type '{}' cannot be used as a map key",
+            type_name
+        )),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn immutable_values_are_map_keys() {
+    assert_last_true(
+        "class A {}
+        a = A()
+        m = {(1, 2): \"t\", 1: \"i\", 1.5: \"f\", true: \"b\", null: \"n\", a: \"instance\"}
+        ok = m[(1, 2)] == \"t\" and m[1.5] == \"f\" and m[null] == \"n\" and m[a] == \"instance\"",
+    );
+}
+
+#[test]
+fn lists_and_maps_are_rejected_as_keys() {
+    assert_unhashable("m = {[1]: 2}", "list");
+    assert_unhashable("m = {}\nm[{\"a\": 1}] = 2", "map");
+}
+
+#[test]
+fn tuples_holding_lists_are_rejected_as_keys() {
+    assert_unhashable("m = {(1, [2]): 3}", "tuple");
+}
+
+#[test]
+fn every_way_of_inserting_checks_the_key() {
+    assert_unhashable("m = {}\nm.insert([1], 2)", "list");
+    assert_unhashable("m = {}\nx = {...m, [1]: 2}", "list");
+}
+
+#[test]
+fn every_way_of_looking_up_checks_the_key() {
+    assert_unhashable("m = {}\nx = [1] in m", "list");
+    assert_unhashable("m = {}\nx = m[[1]]", "list");
+    assert_unhashable("m = {}\nx = m.get([1], 0)", "list");
+    assert_unhashable("m = {}\nx = m.contains({})", "map");
+}
+
+#[test]
+fn instances_stay_found_after_their_attributes_change() {
+    assert_last_true(
+        "class A {}
+        a = A()
+        b = A()
+        m = {a: 1}
+        a.x = 5
+        ok = m[a] == 1 and a in m and b not in m and a == a and (a == b) == false",
+    );
+}
+
+#[test]
+fn tuple_methods() {
+    assert_last_true(
+        "t = (1, 2)
+        ok = t.len() == 2 and t.is_empty() == false and t.to_list() == [1, 2]
+            and [1, 2].to_tuple() == t",
+    );
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn if_else_chains_produce_the_taken_branch() {
+    assert_last_true(
+        "fn sign(n) { return if n < 0 { -1 } else if n == 0 { 0 } else { 1 } }
+        ok = sign(0) == 0 and sign(3) == 1 and sign(-5) + 1 == 0",
+    );
+}
+
+#[test]
+fn missing_else_produces_null() {
+    assert_last_true(
+        "x = if true { 1 }
+        y = if false { 1 }
+        ok = x == 1 and y == null",
+    );
+}
+
+#[test]
+fn if_expressions_nest_inside_other_expressions() {
+    assert_last_true(
+        "z = [if 1 > 0 { \"a\" } else { \"b\" }]
+        w = (if false { 1 } else { 2 }) + 1
+        ok = z == [\"a\"] and w == 3",
+    );
+}
+
+#[test]
+fn branch_value_is_its_last_expression() {
+    assert_last_true(
+        "v = if true { a = 5; a * 2 } else { 0 }
+        ok = v == 10",
+    );
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+const HELPERS: &str = "fn double(x) { return x * 2 }
+fn even(x) { return x % 2 == 0 }
+fn add(a, b) { return a + b }
+fn neg(x) { return 0 - x }
+";
+
+fn assert_with_helpers(source: &str) {
+    assert_last_true(&format!("{}{}", HELPERS, source));
+}
+
+#[test]
+fn map_and_filter_call_back_per_element() {
+    assert_with_helpers(
+        "ok = [1, 2, 3].map(double) == [2, 4, 6] and [1, 2, 3].filter(even) == [2]",
+    );
+}
+
+#[test]
+fn reduce_with_and_without_an_initial_value() {
+    assert_with_helpers("ok = [1, 2, 3].reduce(add) == 6 and [1, 2, 3].reduce(add, 10) == 16");
+}
+
+#[test]
+fn any_and_all_with_and_without_a_predicate() {
+    assert_with_helpers(
+        "ok = [1, 2].any(even) and [1, 2].all(even) == false and [0, 1].any()
+            and [1, 0].all() == false",
+    );
+}
+
+#[test]
+fn sort_by_key_and_in_reverse() {
+    assert_with_helpers(
+        "l = [3, 1, 2]
+        l.sort()
+        m = [3, 1, 2]
+        m.sort(neg)
+        r = [1, 2, 3]
+        r.sort(null, true)
+        ok = l == [1, 2, 3] and m == [3, 2, 1] and r == [3, 2, 1]",
+    );
+}
+
+#[test]
+fn methods_can_be_passed_as_callbacks() {
+    assert_last_true(
+        "class Scale {
+            init(self, by) { self.by = by }
+            fn apply(self, x) { return x * self.by }
+        }
+        ok = [1, 2].map(Scale(3).apply) == [3, 6]",
+    );
+}
+
+#[test]
+fn exceptions_in_callbacks_propagate() {
+    let traceback = run_failing_script(
+        "fn broken(x) { return x + \"a\" }
+        y = [1].map(broken)",
+    );
+    assert!(traceback.contains("line 1, in broken"), "{}", traceback);
+}
+
+#[test]
+fn reduce_of_an_empty_list_raises_value_error() {
+    let traceback = run_failing_script("fn add(a, b) { return a + b }\nx = [].reduce(add)");
+    assert!(
+        traceback.contains("ValueError: List.reduce of an empty list"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn list_literals_keep_runtime_elements_in_source_order() {
+    assert_with_helpers(
+        "a = 1
+        b = 2
+        l = [b, a, double(3)]
+        ok = l[0] == 2 and l[2] == 6 and l == [2, 1, 6]",
+    );
+}
+
+#[test]
+fn call_results_are_kept_as_elements_subscripts_and_values() {
+    assert_with_helpers(
+        "l = [double(1), double(2)]
+        m = {\"a\": double(1)}
+        picked = [5, 6, 7][double(1)]
+        assigned = double(4)
+        ok = l == [2, 4] and m[\"a\"] == 2 and picked == 7 and assigned == 8",
+    );
+}
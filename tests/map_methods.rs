@@ -0,0 +1,53 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+#[test]
+fn keys_keep_insertion_order() {
+    assert_last_true(
+        "m = {\"b\": 1, \"a\": 2}
+        m[\"c\"] = 3
+        m.insert(\"d\", 4)
+        m[\"b\"] = 5
+        ok = m.keys() == [\"b\", \"a\", \"c\", \"d\"] and m.values() == [5, 2, 3, 4]",
+    );
+}
+
+#[test]
+fn removed_keys_leave_the_order_of_the_rest() {
+    assert_last_true(
+        "m = {\"a\": 1, \"b\": 2, \"c\": 3}
+        m.remove(\"b\")
+        popped = m.pop(\"a\")
+        ok = popped == 1 and m.items() == [[\"c\", 3]] and m.len() == 1",
+    );
+}
+
+#[test]
+fn defaults_for_missing_keys() {
+    assert_last_true(
+        "m = {\"a\": 1}
+        set = m.set_default(\"e\", 5)
+        kept = m.set_default(\"a\", 9)
+        ok = set == 5 and kept == 1 and m.get(\"zz\", 0) == 0 and m.pop(\"zz\", 7) == 7",
+    );
+}
+
+#[test]
+fn update_map_values_and_clear() {
+    assert_last_true(
+        "fn double(x) { return x * 2 }
+        m = {\"a\": 1}
+        m.update({\"b\": 2, \"a\": 3})
+        doubled = m.map_values(double)
+        ok = m == {\"a\": 3, \"b\": 2} and doubled[\"a\"] == 6 and m.contains(\"b\")
+        m.clear()
+        ok = ok and m.is_empty()",
+    );
+}
+
+#[test]
+fn missing_keys_raise_key_error() {
+    let traceback = run_failing_script("m = {\"a\": 1}\nx = m.get(\"zz\")");
+    assert!(traceback.contains("KeyError: "), "{}", traceback);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+#[test]
+fn lists_and_tuples_contain_equal_elements() {
+    assert_last_true("ok = 2 in [1, 2] and 3 not in [1, 2] and 1 in (1, 2) and [1] in [[1], [2]]");
+}
+
+#[test]
+fn strings_contain_substrings() {
+    assert_last_true("ok = \"ell\" in \"hello\" and \"z\" not in \"hello\" and \"\" in \"a\"");
+}
+
+#[test]
+fn maps_contain_their_keys() {
+    assert_last_true(
+        "m = {\"k\": 1}
+        ok = \"k\" in m and 1 not in m",
+    );
+}
+
+#[test]
+fn instances_answer_through_their_contains_method() {
+    assert_last_true(
+        "class Bag {
+            init(self, items) { self.items = items }
+            fn contains(self, x) { return x in self.items }
+        }
+        b = Bag([2])
+        ok = 2 in b and 5 not in b",
+    );
+}
+
+#[test]
+fn non_containers_raise_type_error() {
+    let traceback = run_failing_script("x = 1 in 5");
+    assert!(
+        traceback.contains("TypeError: Membership test on a non-container type"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn values_containing_themselves_compare_without_recursing() {
+    // the containers are dropped before the variables are dumped, which would recurse too
+    assert_last_true(
+        "l = [1]
+        l.push(l)
+        other = [1]
+        other.push(other)
+        m = {}
+        m[\"self\"] = m
+        lists = l == l and l == other and l in l and 1 in l
+        maps = m == m and m in [m]
+        l = null
+        other = null
+        m = null
+        ok = lists and maps",
+    );
+}
+
+#[test]
+fn instances_holding_themselves_compare_by_identity() {
+    assert_last_true(
+        "class Node { init(self) { self.me = self } }
+        n = Node()
+        found = n == n and n in [n] and (n == Node()) == false
+        n = null
+        ok = found",
+    );
+}
+
+#[test]
+fn compound_values_compare_unequal() {
+    assert_last_true(
+        "class Point { init(self, x) { self.x = x } }
+        p = Point(1)
+        ok = [1] != [2] and ([1] != [1]) == false and {\"a\": 1} != {} and p != Point(1)",
+    );
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PROJECT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes `files`, as (relative path, source) pairs, into a fresh temporary directory.
+fn project(files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "shriky-modules-{}-{}",
+        std::process::id(),
+        PROJECT_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    for (path, source) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+    root
+}
+
+/// Runs `main.pik` of the project, passing `args` after it, then removes the project.
+fn run_main(root: &Path, args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(root.join("main.pik"))
+        .args(args)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_dir_all(root).ok();
+    output
+}
+
+fn assert_last_true(output: &Output) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        stdout.lines().rfind(|line| line.starts_with("var ")),
+        Some("var Bool(BoolValue(true))"),
+        "{}",
+        stdout
+    );
+}
+
+fn traceback(output: &Output) -> String {
+    assert_eq!(output.status.code(), Some(1));
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+const UTIL: &str = "counter = 0
+fn double(x) { return x * 2 }
+counter = counter + 1";
+
+#[test]
+fn import_binds_the_module_by_name_or_alias() {
+    let root = project(&[
+        ("lib/util.pik", UTIL),
+        (
+            "main.pik",
+            "import \"lib/util\"
+            import \"lib/util\" as u
+            ok = util.double(2) == 4 and u.double(3) == 6",
+        ),
+    ]);
+    assert_last_true(&run_main(&root, &[]));
+}
+
+#[test]
+fn from_import_binds_names_and_runs_the_module_once() {
+    let root = project(&[
+        ("lib/util.pik", UTIL),
+        (
+            "main.pik",
+            "import \"lib/util\"
+            from \"lib/util\" import double, counter
+            ok = double(5) == 10 and counter == 1",
+        ),
+    ]);
+    assert_last_true(&run_main(&root, &[]));
+}
+
+#[test]
+fn search_paths_are_tried_after_the_entry_directory() {
+    let root = project(&[
+        ("vendor/util.pik", UTIL),
+        ("app/main.pik", "import \"util\"\nok = util.double(1) == 2"),
+    ]);
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(root.join("app/main.pik"))
+        .arg("--path")
+        .arg(root.join("vendor"))
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_dir_all(&root).ok();
+    assert_last_true(&output);
+}
+
+#[test]
+fn missing_modules_raise_import_error() {
+    let root = project(&[("main.pik", "import \"missing\"")]);
+    let traceback = traceback(&run_main(&root, &[]));
+    assert!(
+        traceback.contains("ImportError: Module \"missing\" not found"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn circular_imports_raise_import_error() {
+    let root = project(&[
+        ("main.pik", "import \"a\""),
+        ("a.pik", "import \"b\""),
+        ("b.pik", "import \"a\""),
+    ]);
+    let traceback = traceback(&run_main(&root, &[]));
+    assert!(
+        traceback.contains("ImportError: Circular import: "),
+        "{}",
+        traceback
+    );
+}
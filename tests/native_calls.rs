@@ -0,0 +1,12 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn native_methods_receive_arguments_in_call_order() {
+    assert_last_true(
+        "m = {}
+        m.insert(\"a\", 1)
+        ok = m[\"a\"] == 1 and m.get(\"a\") == 1",
+    );
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn int_arithmetic_stays_int() {
+    assert_last_true(
+        "x = 7 * 6 - 2 + 1
+        same = x == 41 && x / 2 == 20.5 && x % 2 == 1",
+    );
+}
+
+#[test]
+fn mixed_arithmetic_promotes_to_float() {
+    assert_last_true(
+        "a = 1 + 0.5
+        b = 0.5 * 4
+        c = 3 - 1.5
+        promoted = a == 1.5 && b == 2 && c == 1.5 && a > 1 && 2 >= b && 1 < a",
+    );
+}
+
+#[test]
+fn loop_counters_are_values() {
+    assert_last_true(
+        "i = 0
+        seen = [];
+        while i < 3 {
+            seen.push(i)
+            i += 1
+        }
+        independent = seen == [0, 1, 2] && i == 3",
+    );
+}
+
+#[test]
+fn comparisons_of_mixed_types() {
+    assert_last_true(
+        "strings = \"a\" < \"b\"
+        unequal = (1 == \"1\") == false
+        compared = strings && unequal && null == null",
+    );
+}
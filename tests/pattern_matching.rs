@@ -0,0 +1,58 @@
+mod common;
+
+use common::assert_last_true;
+
+const DESCRIBE: &str = "class Point { init(self, x, y) { self.x = x; self.y = y } }
+fn describe(v) {
+    return match v {
+        0 | 1 => \"small\",
+        [a, b] => a + b,
+        {\"k\": k} => k,
+        Point(px, py) if px == py => \"diagonal\",
+        Point(px, _) => px,
+        n if n > 100 => \"big\",
+        _ => \"other\",
+    }
+}
+";
+
+fn assert_describes(checks: &str) {
+    assert_last_true(&format!("{}ok = {}", DESCRIBE, checks));
+}
+
+#[test]
+fn literal_and_or_patterns() {
+    assert_describes("describe(0) == \"small\" and describe(1) == \"small\"");
+}
+
+#[test]
+fn sequence_and_map_patterns_bind_names() {
+    assert_describes("describe([2, 3]) == 5 and describe({\"k\": \"v\"}) == \"v\"");
+}
+
+#[test]
+fn class_patterns_bind_init_parameters() {
+    assert_describes("describe(Point(2, 2)) == \"diagonal\" and describe(Point(4, 1)) == 4");
+}
+
+#[test]
+fn guards_and_wildcards() {
+    assert_describes("describe(500) == \"big\" and describe(7) == \"other\"");
+}
+
+#[test]
+fn unmatched_subjects_produce_null() {
+    assert_last_true(
+        "r = match 5 { 1 => \"one\" }
+        ok = r == null",
+    );
+}
+
+#[test]
+fn match_as_a_statement() {
+    assert_last_true(
+        "seen = 0
+        match [1, 2] { [x, y] => { seen = x + y } }
+        ok = seen == 3",
+    );
+}
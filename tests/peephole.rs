@@ -0,0 +1,128 @@
+mod common;
+
+use common::run_script_with_args;
+
+/// The dumped variables of a run at `level`, leaving out functions and classes, whose listed
+/// operations differ between levels by design.
+fn values_at(source: &str, level: &str) -> Vec<String> {
+    run_script_with_args(source, &["--opt-level", level])
+        .lines()
+        .filter_map(|line| line.strip_prefix("var "))
+        .filter(|value| !value.starts_with("Function(") && !value.starts_with("Class("))
+        .map(String::from)
+        .collect()
+}
+
+fn assert_same_at_every_level(source: &str) {
+    let unoptimized = values_at(source, "0");
+    assert!(!unoptimized.is_empty(), "script:\n{}", source);
+    for level in ["1", "2"] {
+        assert_eq!(
+            values_at(source, level),
+            unoptimized,
+            "level {}, script:\n{}",
+            level,
+            source
+        );
+    }
+}
+
+/// The dumped operations of the script's function taking `parameters`.
+fn function_operations(source: &str, parameters: &str) -> String {
+    let output = run_script_with_args(source, &[]);
+    let prefix = format!("var Function(<{}>", parameters);
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map(String::from)
+        .expect("function not dumped")
+}
+
+#[test]
+fn loops_with_returns_inside_run_the_same() {
+    assert_same_at_every_level(
+        "fn first_over(limit) {
+            i = 0
+            while true {
+                if i * i > limit { return i }
+                i = i + 1
+            }
+            return -1
+        }
+        fn never() { while false { return 1 }; 2 }
+        found = first_over(50)
+        skipped = never()",
+    );
+}
+
+#[test]
+fn nested_branches_run_the_same() {
+    assert_same_at_every_level(
+        "fn classify(x) {
+            label = \"small\"
+            if x > 1 {
+                if x > 2 {
+                    if x > 3 { label = \"huge\" } else { label = \"big\" }
+                }
+            } else {
+                label = \"tiny\"
+            }
+            return label
+        }
+        labels = [classify(0), classify(2), classify(3), classify(4)]
+        picked = if labels[0] == \"tiny\" { 1 } else { 2 }",
+    );
+}
+
+#[test]
+fn matches_and_loops_run_the_same() {
+    assert_same_at_every_level(
+        "fn describe(value) {
+            return match value {
+                [a, b] => a + b,
+                {\"k\": v} => v,
+                0 => \"zero\",
+                _ => \"other\",
+            }
+        }
+        described = [describe([1, 2]), describe({\"k\": 3}), describe(0), describe(9)]
+        total = 0
+        n = 0
+        while n < 10 { n = n + 1; if n > 5 { total = total + n } }",
+    );
+}
+
+#[test]
+fn constant_loop_conditions_are_dropped() {
+    let operations = function_operations(
+        "fn spin(n) { i = 0; while true { if i > n { return i }; i = i + 1 }; return 99 }",
+        "n",
+    );
+    assert_eq!(
+        operations.matches("PopJumpIfFalse").count(),
+        1,
+        "{}",
+        operations
+    );
+}
+
+#[test]
+fn unreachable_operations_after_returns_are_removed() {
+    let operations = function_operations("fn early(x) { return x; x = 2; return x }", "x");
+    assert_eq!(operations, "[[LoadLocal, 0], [ReturnValue, 0]])");
+}
+
+#[test]
+fn jump_chains_are_threaded() {
+    let operations =
+        function_operations("fn nested(x) { if x > 1 { if x > 2 { y = 3 } }; y }", "x");
+    // both conditions skip straight to the final load, and no jump lands on another jump
+    let targets: Vec<&str> = operations
+        .split("[PopJumpIfFalse, ")
+        .skip(1)
+        .filter_map(|rest| rest.split_once(']').map(|(target, _)| target))
+        .collect();
+    assert_eq!(targets.len(), 2, "{}", operations);
+    assert_eq!(targets[0], targets[1], "{}", operations);
+    assert!(!operations.contains("[Jump, "), "{}", operations);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+#[test]
+fn deep_recursion_does_not_overflow() {
+    assert_last_true(
+        "fn depth(n) {
+            if n == 0 { return 0 }
+            return 1 + depth(n - 1)
+        }
+        deep = depth(9000) == 9000",
+    );
+}
+
+#[test]
+fn deep_method_recursion_does_not_overflow() {
+    assert_last_true(
+        "class Counter {
+            fn down(self, n) {
+                if n == 0 { return 0 }
+                return 1 + self.down(n - 1)
+            }
+        }
+        deep = Counter().down(5000) == 5000",
+    );
+}
+
+#[test]
+fn calling_a_class_results_in_the_instance() {
+    assert_last_true(
+        "class Node {
+            init(self, next) {
+                self.next = next
+                return 42
+            }
+        }
+        fn chain(n) {
+            if n == 0 { return null }
+            return Node(chain(n - 1))
+        }
+        head = chain(3)
+        linked = head.next.next.next == null",
+    );
+}
+
+#[test]
+fn unbounded_recursion_raises_recursion_error() {
+    let output = run_failing_script(
+        "fn forever(n) { return 1 + forever(n + 1) }
+        forever(0)",
+    );
+    assert!(output.contains("RecursionError"), "{}", output);
+}
+
+#[test]
+fn recursion_through_native_callbacks_within_the_limit() {
+    assert_last_true(
+        "fn f(n) {
+            if n == 0 { return 0 }
+            return [n - 1].map(f)[0] + 1
+        }
+        deep = f(300) == 300",
+    );
+}
+
+fn assert_native_recursion_error(source: &str) {
+    let output = run_failing_script(source);
+    assert!(
+        output.contains("RecursionError: Maximum depth of 500 nested calls from native code"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn unbounded_recursion_through_map_raises_recursion_error() {
+    assert_native_recursion_error(
+        "fn f(n) {
+            if n == 0 { return 0 }
+            return [n - 1].map(f)[0] + 1
+        }
+        x = f(4000)",
+    );
+}
+
+#[test]
+fn unbounded_recursion_through_sort_keys_raises_recursion_error() {
+    assert_native_recursion_error(
+        "fn key(n) {
+            if n == 0 { return 0 }
+            l = [n - 1]
+            l.sort(key)
+            return n
+        }
+        x = key(4000)",
+    );
+}
+
+#[test]
+fn unbounded_recursion_through_contains_raises_recursion_error() {
+    assert_native_recursion_error(
+        "class Down {
+            fn contains(self, n) {
+                if n == 0 { return true }
+                return n - 1 in self
+            }
+        }
+        x = 4000 in Down()",
+    );
+}
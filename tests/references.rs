@@ -0,0 +1,63 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn writes_through_a_reference_argument() {
+    assert_last_true(
+        "fn inc(r) { *r += 1 }
+        x = 1
+        inc(&x)
+        incremented = x == 2",
+    );
+}
+
+#[test]
+fn reference_follows_rebinding_of_the_variable() {
+    assert_last_true(
+        "x = 1
+        r = &x
+        x = 10
+        follows = *r == 10",
+    );
+}
+
+#[test]
+fn reference_outlives_its_scope() {
+    assert_last_true(
+        "fn make() {
+            local = 5
+            return &local
+        }
+        r = make()
+        *r += 1
+        alive = *r == 6",
+    );
+}
+
+#[test]
+fn references_to_enclosing_scope_variables() {
+    assert_last_true(
+        "fn swap(a, b) {
+            t = *a
+            *a = *b
+            *b = t
+        }
+        p = 1
+        q = 2
+        swap(&p, &q)
+        swapped = p == 2 && q == 1",
+    );
+}
+
+#[test]
+fn deref_assignment_starts_a_new_line() {
+    assert_last_true(
+        "x = 1
+        r = &x
+        *r = 5
+        y = 2 * 3
+        *r = *r * y
+        written = x == 30",
+    );
+}
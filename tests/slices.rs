@@ -0,0 +1,87 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script, write_script};
+use std::process::Command;
+
+#[test]
+fn list_slices_copy_the_selected_elements() {
+    assert_last_true(
+        "l = [1, 2, 3, 4, 5]
+        ok = l[1:3] == [2, 3] and l[:2] == [1, 2] and l[3:] == [4, 5] and l[:] == l",
+    );
+}
+
+#[test]
+fn negative_bounds_and_steps() {
+    assert_last_true(
+        "l = [1, 2, 3, 4, 5]
+        ok = l[-2:] == [4, 5] and l[::2] == [1, 3, 5] and l[::-1] == [5, 4, 3, 2, 1]
+            and l[10:] == [] and l[-10:1] == [1]",
+    );
+}
+
+#[test]
+fn string_slices_count_chars() {
+    assert_last_true(
+        "s = \"héllo\"
+        ok = s[1:4] == \"éll\" and s[::-1] == \"olléh\" and s[-1] == \"o\"",
+    );
+}
+
+#[test]
+fn slice_assignment_replaces_the_range() {
+    assert_last_true(
+        "l = [1, 2, 3, 4, 5]
+        l[1:3] = [9]
+        m = [1, 2, 3, 4]
+        m[::2] = [7, 8]
+        ok = l == [1, 9, 4, 5] and m == [7, 2, 8, 4]",
+    );
+}
+
+#[test]
+fn negative_list_indexes_count_from_the_end() {
+    assert_last_true(
+        "l = [1, 2, 3]
+        ok = l[-1] == 3 and l[-3] == 1",
+    );
+}
+
+#[test]
+fn out_of_range_list_indexes_raise_index_error() {
+    for index in ["3", "-4"] {
+        let traceback = run_failing_script(&format!("l = [1, 2, 3]\nx = l[{}]", index));
+        assert!(
+            traceback.contains(&format!("IndexError: List index {} out of range", index)),
+            "{}",
+            traceback
+        );
+    }
+}
+
+#[test]
+fn zero_slice_step_raises_value_error() {
+    let traceback = run_failing_script("l = [1, 2, 3]\nx = l[::0]");
+    assert!(
+        traceback.contains("ValueError: Slice step cannot be zero"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn empty_subscript_is_a_syntax_error() {
+    let script = write_script("l = [1, 2, 3]\nx = l[]");
+    let output = Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .arg(&script)
+        .output()
+        .expect("failed to run the interpreter");
+    std::fs::remove_file(&script).ok();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("Syntax error on line 2: Expected a subscript expression"),
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+#[test]
+fn call_arguments_spread_from_any_iterable() {
+    assert_last_true(
+        "fn f(a, b, c) { return a + b * c }
+        fn cat(a, b, c) { return a + b + c }
+        args = [1, 2]
+        ok = f(...args, 3) == 7 and f(1, ...(2, 3)) == 7 and cat(...\"abc\") == \"abc\"",
+    );
+}
+
+#[test]
+fn list_literals_keep_source_order_around_spreads() {
+    assert_last_true(
+        "n = 3
+        l = [0, ...(1, 2), n, ...[4]]
+        k = [...\"ab\", ...{\"z\": 1}]
+        ok = l == [0, 1, 2, 3, 4] and k == [\"a\", \"b\", \"z\"]",
+    );
+}
+
+#[test]
+fn later_map_entries_override_spread_ones() {
+    assert_last_true(
+        "d = {\"a\": 1, \"b\": 2}
+        m = {...d, \"b\": 3, \"c\": 4}
+        ok = m == {\"a\": 1, \"b\": 3, \"c\": 4} and m.keys() == [\"a\", \"b\", \"c\"] and d[\"b\"] == 2",
+    );
+}
+
+#[test]
+fn only_maps_spread_into_maps() {
+    let traceback = run_failing_script("x = {...[1]}");
+    assert!(
+        traceback.contains("TypeError: Only maps can be spread into a map, got 'list'"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn non_iterables_cannot_be_spread() {
+    let traceback = run_failing_script("x = [...5]");
+    assert!(
+        traceback.contains("TypeError: 'int' value is not iterable"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn spread_arguments_are_counted() {
+    let traceback = run_failing_script("fn f(a) { return a }\nx = f(...[1, 2])");
+    assert!(
+        traceback.contains("ArgumentError: Callable expected 1 arguments, got 2"),
+        "{}",
+        traceback
+    );
+}
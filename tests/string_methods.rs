@@ -0,0 +1,61 @@
+mod common;
+
+use common::{assert_last_true, run_failing_script};
+
+#[test]
+fn case_and_whitespace() {
+    assert_last_true(
+        "t = \"  Hello, World  \".strip()
+        ok = t == \"Hello, World\" and t.upper() == \"HELLO, WORLD\"
+            and t.lower() == \"hello, world\"",
+    );
+}
+
+#[test]
+fn splitting_joining_and_replacing() {
+    assert_last_true(
+        "ok = \"a,b,c\".split(\",\") == [\"a\", \"b\", \"c\"] and \"-\".join([\"a\", \"b\"]) == \"a-b\"
+            and \"aXbX\".replace(\"X\", \"y\") == \"ayby\" and \"ab\".repeat(3) == \"ababab\"",
+    );
+}
+
+#[test]
+fn searching() {
+    assert_last_true(
+        "ok = \"hello\".find(\"l\") == 2 and \"hello\".find(\"z\") + 1 == 0
+            and \"hello\".starts_with(\"he\") and \"hello\".ends_with(\"lo\")
+            and \"hello\".contains(\"ell\")",
+    );
+}
+
+#[test]
+fn formatting_fills_placeholders_in_order() {
+    assert_last_true("ok = \"{} + {}\".format(1, 2) == \"1 + 2\"");
+}
+
+#[test]
+fn lengths_and_indexes_count_chars() {
+    assert_last_true(
+        "ok = \"héllo\".len() == 5 and \"héllo\"[1] == \"é\" and \"héy\".chars() == [\"h\", \"é\", \"y\"]",
+    );
+}
+
+#[test]
+fn out_of_range_indexes_raise_index_error() {
+    let traceback = run_failing_script("x = \"abc\"[5]");
+    assert!(
+        traceback.contains("IndexError: String index 5 is out of bounds for length 3"),
+        "{}",
+        traceback
+    );
+}
+
+#[test]
+fn unknown_methods_raise_attribute_error() {
+    let traceback = run_failing_script("x = \"abc\".nope()");
+    assert!(
+        traceback.contains("AttributeError: Attribute not found: nope"),
+        "{}",
+        traceback
+    );
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::assert_last_true;
+
+#[test]
+fn self_tail_recursion_runs_past_the_recursion_limit() {
+    assert_last_true(
+        "fn count(n, acc) {
+            if n == 0 { return acc }
+            return count(n - 1, acc + 1)
+        }
+        counted = count(50000, 0) == 50000",
+    );
+}
+
+#[test]
+fn mutual_tail_recursion_runs_past_the_recursion_limit() {
+    assert_last_true(
+        "is_odd = null
+        fn is_even(n) { if n == 0 { return true }; return is_odd(n - 1) }
+        fn is_odd(n) { if n == 0 { return false }; return is_even(n - 1) }
+        even = is_even(30000)",
+    );
+}
+
+#[test]
+fn method_tail_calls_keep_their_instance() {
+    assert_last_true(
+        "class Counter {
+            fn down(self, n) {
+                if n == 0 { return self.name }
+                return self.down(n - 1)
+            }
+        }
+        c = Counter()
+        c.name = \"counter\"
+        named = c.down(40000) == \"counter\"",
+    );
+}
+
+#[test]
+fn init_tail_call_still_results_in_the_instance() {
+    assert_last_true(
+        "class Point {
+            init(self, x) { self.x = x; return self.describe() }
+            fn describe(self) { return 42 }
+        }
+        kept = Point(3).x == 3",
+    );
+}
+
+#[test]
+fn tail_calls_to_nested_functions_see_their_scope() {
+    assert_last_true(
+        "fn apply(f) { return f() }
+        fn outer(n) {
+            fn inner() { return n * 2 }
+            return apply(inner)
+        }
+        doubled = outer(4) == 8",
+    );
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::run_failing_script;
+
+#[test]
+fn traceback_lists_frames_most_recent_last() {
+    let output = run_failing_script(
+        "fn inner(x) {
+            y = x + 1
+            return y + \"a\"
+        }
+        fn outer(x) {
+            return 1 + inner(x)
+        }
+        r = outer(3)",
+    );
+    let frames: Vec<&str> = output
+        .lines()
+        .filter(|line| line.trim_start().starts_with("File "))
+        .collect();
+    assert_eq!(frames.len(), 3, "{}", output);
+    assert!(frames[0].ends_with("line 8, in <module>"), "{}", output);
+    assert!(frames[1].ends_with("line 6, in outer"), "{}", output);
+    assert!(frames[2].ends_with("line 3, in inner"), "{}", output);
+}
+
+#[test]
+fn traceback_quotes_the_failing_source_line() {
+    let output = run_failing_script(
+        "total = 1
+        total = total + \"text\"",
+    );
+    assert!(
+        output.contains("\n    total = total + \"text\"\n"),
+        "{}",
+        output
+    );
+    assert!(output.contains("\nInvalidOperation: "), "{}", output);
+}
+
+#[test]
+fn methods_are_named_after_their_class() {
+    let output = run_failing_script(
+        "fn fail() { return 1 + \"x\" }
+        class Widget {
+            fn draw(self) { return 1 + fail() }
+        }
+        Widget().draw()",
+    );
+    assert!(output.contains("line 3, in Widget.draw"), "{}", output);
+    assert!(output.contains("line 1, in fail"), "{}", output);
+}
+
+#[test]
+fn repeated_frames_are_summarized() {
+    let output = run_failing_script(
+        "fn forever(n) { return 1 + forever(n + 1) }
+        forever(0)",
+    );
+    assert!(output.contains("[Previous line repeated"), "{}", output);
+    assert!(output.lines().count() < 20, "{}", output);
+}
+
+#[test]
+fn unsupported_operands_raise_instead_of_panicking() {
+    assert!(run_failing_script("x = [1] - 2").contains("\nInvalidOperation: "));
+    assert!(
+        run_failing_script(
+            "x = 5
+            class B(x) {}"
+        )
+        .contains("\nTypeError: Classes can only inherit from classes")
+    );
+    assert!(
+        run_failing_script(
+            "class A {}
+            y = A.x"
+        )
+        .contains("\nAttributeError: ")
+    );
+}
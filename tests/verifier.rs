@@ -0,0 +1,296 @@
+mod common;
+
+use common::write_script;
+use std::path::Path;
+use std::process::{Command, Output};
+
+const LOAD_CONSTANT: u8 = 0;
+const LOAD_LOCAL: u8 = 1;
+const LOAD_NULL: u8 = 4;
+const MAKE_MAP: u8 = 11;
+const MAKE_CLASS: u8 = 12;
+const COMPARE: u8 = 28;
+const ACCESS_ATTRIBUTE: u8 = 38;
+const PRE_ASSIGN: u8 = 39;
+const ASSIGN_ATTRIBUTE: u8 = 42;
+const POP: u8 = 48;
+const POP_JUMP_IF_FALSE: u8 = 49;
+
+fn interpreter(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ShrikyInterpreter"))
+        .args(args)
+        .output()
+        .expect("failed to run the interpreter")
+}
+
+const TAG_FUNCTION: u8 = 7;
+const TAG_METHOD: u8 = 8;
+
+fn push_str(bytes: &mut Vec<u8>, string: &str) {
+    bytes.push(string.len() as u8);
+    bytes.extend_from_slice(string.as_bytes());
+}
+
+/// A code object named `name` running `code`, holding the already encoded `constants` and the
+/// `variables`, each looked up by its name.
+fn code_object(
+    id: u8,
+    name: &str,
+    code: &[u8],
+    constants: &[Vec<u8>],
+    variables: &[&str],
+) -> Vec<u8> {
+    let mut bytes = vec![id];
+    push_str(&mut bytes, name);
+    bytes.push(0); // file
+    bytes.push(code.len() as u8);
+    bytes.extend_from_slice(code);
+    bytes.push(constants.len() as u8);
+    for constant in constants {
+        bytes.extend_from_slice(constant);
+    }
+    bytes.push(variables.len() as u8);
+    for variable in variables {
+        push_str(&mut bytes, variable);
+    }
+    bytes.push(0); // constant lookup
+    bytes.push(variables.len() as u8);
+    for (index, variable) in variables.iter().enumerate() {
+        push_str(&mut bytes, variable);
+        bytes.push(index as u8);
+    }
+    bytes.push(0); // line table
+    bytes
+}
+
+/// A function (or method, by `tag`) constant taking `parameters`, with an empty body holding
+/// `variables`.
+fn function(tag: u8, parameters: &[&str], variables: &[&str]) -> Vec<u8> {
+    let mut bytes = vec![tag, parameters.len() as u8];
+    for parameter in parameters {
+        push_str(&mut bytes, parameter);
+    }
+    bytes.extend_from_slice(&code_object(1, "f", &[], &[], variables));
+    bytes
+}
+
+/// A compiled file holding a `<module>` code object that loads its single constant.
+fn module_with_constant(constant: Vec<u8>) -> Vec<u8> {
+    compiled(code_object(
+        0,
+        "<module>",
+        &[LOAD_CONSTANT, 0, POP],
+        &[constant],
+        &[],
+    ))
+}
+
+/// A compiled file holding a single `<module>` code object with `code`, no constants and
+/// `variables` variables.
+fn module(code: &[u8], variables: usize) -> Vec<u8> {
+    let names: Vec<String> = (0..variables)
+        .map(|i| ((b'a' + i as u8) as char).to_string())
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    compiled(code_object(0, "<module>", code, &[], &names))
+}
+
+fn compiled(module: Vec<u8>) -> Vec<u8> {
+    let mut bytes = b"SHRC".to_vec();
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&module);
+    bytes
+}
+
+/// Runs `bytes` as a compiled file.
+fn run_compiled(bytes: &[u8]) -> Output {
+    let path = write_script("").with_extension("shrc");
+    std::fs::write(&path, bytes).unwrap();
+    let output = interpreter(&[&path]);
+    std::fs::remove_file(&path).ok();
+    output
+}
+
+/// Loads a module running `code` and returns the error the interpreter reported.
+fn rejection(code: &[u8], variables: usize) -> String {
+    file_rejection(&module(code, variables))
+}
+
+/// Loads the compiled file and returns the error the interpreter reported.
+fn file_rejection(bytes: &[u8]) -> String {
+    let output = run_compiled(bytes);
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+    assert!(stderr.contains("Cannot load"), "{}", stderr);
+    stderr
+}
+
+/// Runs a module running `code`, which loads but fails while it runs, and returns the traceback.
+fn runtime_error(code: &[u8], variables: usize) -> String {
+    let output = run_compiled(&module(code, variables));
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(
+        stderr.starts_with("Traceback (most recent call last)"),
+        "{}",
+        stderr
+    );
+    stderr
+}
+
+#[test]
+fn well_formed_code_loads() {
+    let output = run_compiled(&module(&[LOAD_NULL, POP], 0));
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn out_of_range_operands_are_rejected() {
+    assert!(
+        rejection(&[LOAD_CONSTANT, 0], 0)
+            .contains("<module> at offset 0: constant 0 out of range (0 constants)")
+    );
+    assert!(
+        rejection(&[LOAD_NULL, POP, LOAD_LOCAL, 2], 2)
+            .contains("<module> at offset 2: variable 2 out of range (2 variables)")
+    );
+    assert!(
+        rejection(&[LOAD_NULL, LOAD_NULL, COMPARE, 9], 0)
+            .contains("<module> at offset 2: invalid comparison 9")
+    );
+    // 256 as a two byte varint, which must not wrap around to a valid comparison
+    assert!(
+        rejection(&[LOAD_NULL, LOAD_NULL, COMPARE, 0x80, 0x02], 0)
+            .contains("<module> at offset 2: invalid comparison 256")
+    );
+}
+
+#[test]
+fn maps_with_a_key_without_a_value_are_rejected() {
+    assert!(
+        rejection(&[LOAD_NULL, MAKE_MAP, 1, POP], 0)
+            .contains("<module> at offset 1: map of 1 values has a key without a value")
+    );
+}
+
+#[test]
+fn functions_with_unbound_parameters_are_rejected() {
+    assert!(
+        file_rejection(&module_with_constant(function(TAG_FUNCTION, &["x"], &[])))
+            .contains("parameter x is not a variable")
+    );
+    assert!(
+        file_rejection(&module_with_constant(function(
+            TAG_METHOD,
+            &["self", "x"],
+            &["self"]
+        )))
+        .contains("parameter x is not a variable")
+    );
+}
+
+#[test]
+fn methods_without_self_are_rejected() {
+    assert!(
+        file_rejection(&module_with_constant(function(TAG_METHOD, &[], &[])))
+            .contains("f at offset 0: method without a self parameter")
+    );
+}
+
+#[test]
+fn well_formed_functions_load() {
+    for constant in [
+        function(TAG_FUNCTION, &["x"], &["x"]),
+        function(TAG_METHOD, &["self"], &["self"]),
+    ] {
+        let output = run_compiled(&module_with_constant(constant));
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn unbalanced_stacks_are_rejected() {
+    assert!(
+        rejection(&[POP], 0).contains("<module> at offset 0: pops 1 values with 0 on the stack")
+    );
+    // the fall-through path reaches the end with a value the jump skips
+    assert!(
+        rejection(&[LOAD_NULL, POP_JUMP_IF_FALSE, 4, LOAD_NULL], 0).contains(
+            "<module> at offset 4: reached with 1 values on the stack, 0 on another path"
+        )
+    );
+}
+
+#[test]
+fn branching_statements_compile_to_verified_code() {
+    let script = write_script(
+        "fn pick(x) {
+    if x { 1 }
+    match x { true => 2, _ => { 3; 4 } }
+    if x { 5; 6 } else { 7 }
+}
+total = 0
+i = 0
+while i < 3 { i = i + 1; total = total + i; 8 }
+if total > 5 { total } else { \"small\" }
+picked = [pick(true), pick(false)]",
+    );
+    let compiled = script.with_extension("shrc");
+    let compile = interpreter(&[Path::new("compile"), &script]);
+    std::fs::remove_file(&script).ok();
+    assert!(
+        compile.status.success(),
+        "{}",
+        String::from_utf8_lossy(&compile.stderr)
+    );
+    let output = interpreter(&[&compiled]);
+    std::fs::remove_file(&compiled).ok();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let values: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("var "))
+        .filter(|value| !value.starts_with("Function("))
+        .collect();
+    assert_eq!(values.len(), 3, "{}", stdout);
+    assert_eq!(values[0], "Int(IntValue(6))");
+    assert!(
+        values[2].contains("6") && values[2].contains("7"),
+        "{}",
+        values[2]
+    );
+}
+
+#[test]
+fn classes_made_of_other_values_raise() {
+    // the class loaded from its variable before the constant is assigned to it
+    assert!(
+        runtime_error(&[LOAD_LOCAL, 0, MAKE_CLASS, 0, PRE_ASSIGN, 0], 1)
+            .contains("TypeError: Cannot make a class of a non-class value")
+    );
+}
+
+#[test]
+fn attribute_names_of_other_values_raise() {
+    assert!(
+        runtime_error(&[LOAD_NULL, LOAD_NULL, ACCESS_ATTRIBUTE, POP], 0)
+            .contains("TypeError: Attribute names must be strings")
+    );
+    assert!(
+        runtime_error(&[LOAD_NULL, LOAD_NULL, LOAD_NULL, ASSIGN_ATTRIBUTE], 0)
+            .contains("TypeError: Attribute names must be strings")
+    );
+}